@@ -0,0 +1,146 @@
+use x86_64::instructions::port::{Port, PortReadOnly, PortWriteOnly};
+
+use crate::high_score::SECTOR_SIZE;
+
+/// Status register bits.
+const BSY: u8 = 0x80;
+const DRQ: u8 = 0x08;
+const ERR: u8 = 0x01;
+const DF: u8 = 0x20;
+
+const IDENTIFY: u8 = 0xEC;
+const READ_SECTORS: u8 = 0x20;
+const WRITE_SECTORS: u8 = 0x30;
+const CACHE_FLUSH: u8 = 0xE7;
+
+/// Status polls before a command is given up on, so a missing or wedged
+/// drive can't hang the game.
+const TIMEOUT: usize = 100_000;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum AtaError {
+    /// Nothing answered, or what did isn't an ATA disk.
+    NoDrive,
+    /// The drive reported an error.
+    Drive,
+    Timeout,
+}
+
+/// A drive on an ATA bus, driven by polled PIO with 28-bit LBA.
+pub struct AtaDrive {
+    data: Port<u16>,
+    features: PortWriteOnly<u8>,
+    sector_count: PortWriteOnly<u8>,
+    lba_low: Port<u8>,
+    lba_mid: Port<u8>,
+    lba_high: Port<u8>,
+    drive: PortWriteOnly<u8>,
+    command: PortWriteOnly<u8>,
+    status: PortReadOnly<u8>,
+    slave: bool,
+}
+
+impl AtaDrive {
+    /// The drive on the primary bus at I/O port `0x1F0`, the master or slave.
+    /// QEMU attaches `-drive ...,index=1` as the primary slave.
+    pub const fn primary(slave: bool) -> Self {
+        const BASE: u16 = 0x1F0;
+        Self {
+            data: Port::new(BASE),
+            features: PortWriteOnly::new(BASE + 1),
+            sector_count: PortWriteOnly::new(BASE + 2),
+            lba_low: Port::new(BASE + 3),
+            lba_mid: Port::new(BASE + 4),
+            lba_high: Port::new(BASE + 5),
+            drive: PortWriteOnly::new(BASE + 6),
+            command: PortWriteOnly::new(BASE + 7),
+            status: PortReadOnly::new(BASE + 7),
+            slave,
+        }
+    }
+
+    /// Checks that an ATA disk is attached.
+    pub fn identify(&mut self) -> Result<(), AtaError> {
+        unsafe {
+            self.drive.write(if self.slave { 0xB0 } else { 0xA0 });
+            self.sector_count.write(0);
+            self.lba_low.write(0);
+            self.lba_mid.write(0);
+            self.lba_high.write(0);
+            self.command.write(IDENTIFY);
+            let status = self.status.read();
+            if status == 0 || status == 0xFF {
+                return Err(AtaError::NoDrive);
+            }
+            self.wait_while_busy()?;
+            if self.lba_mid.read() != 0 || self.lba_high.read() != 0 {
+                return Err(AtaError::NoDrive);
+            }
+            self.wait_for_data()?;
+            for _ in 0..SECTOR_SIZE / 2 {
+                self.data.read();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn read_sector(&mut self, lba: u32, buffer: &mut [u8; SECTOR_SIZE]) -> Result<(), AtaError> {
+        unsafe {
+            self.start(lba, READ_SECTORS)?;
+            self.wait_for_data()?;
+            for pair in buffer.chunks_exact_mut(2) {
+                pair.copy_from_slice(&self.data.read().to_le_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write_sector(&mut self, lba: u32, buffer: &[u8; SECTOR_SIZE]) -> Result<(), AtaError> {
+        unsafe {
+            self.start(lba, WRITE_SECTORS)?;
+            self.wait_for_data()?;
+            for pair in buffer.chunks_exact(2) {
+                self.data.write(u16::from_le_bytes([pair[0], pair[1]]));
+            }
+            self.command.write(CACHE_FLUSH);
+            self.wait_while_busy()
+        }
+    }
+
+    /// Selects the drive and sends a one-sector `command` at `lba`.
+    unsafe fn start(&mut self, lba: u32, command: u8) -> Result<(), AtaError> {
+        let slave = if self.slave { 0x10 } else { 0 };
+        self.drive.write(0xE0 | slave | ((lba >> 24) & 0x0F) as u8);
+        self.wait_while_busy()?;
+        self.features.write(0);
+        self.sector_count.write(1);
+        self.lba_low.write(lba as u8);
+        self.lba_mid.write((lba >> 8) as u8);
+        self.lba_high.write((lba >> 16) as u8);
+        self.command.write(command);
+        Ok(())
+    }
+
+    unsafe fn wait_while_busy(&mut self) -> Result<(), AtaError> {
+        for _ in 0..TIMEOUT {
+            let status = self.status.read();
+            if status & BSY == 0 {
+                return if status & (ERR | DF) != 0 { Err(AtaError::Drive) } else { Ok(()) };
+            }
+        }
+        Err(AtaError::Timeout)
+    }
+
+    unsafe fn wait_for_data(&mut self) -> Result<(), AtaError> {
+        for _ in 0..TIMEOUT {
+            let status = self.status.read();
+            if status & (ERR | DF) != 0 {
+                return Err(AtaError::Drive);
+            }
+            if status & BSY == 0 && status & DRQ != 0 {
+                return Ok(());
+            }
+        }
+        Err(AtaError::Timeout)
+    }
+}
@@ -0,0 +1,194 @@
+use num::Integer;
+use crate::sweep::{sweep, Aabb, Contact, Move};
+use crate::screen::{Color, ColorCode, Screen, BUFFER_HEIGHT, BUFFER_WIDTH};
+
+/// Fractions of a cell positions and speeds are kept in.
+pub const SUBCELL: isize = 256;
+
+/// `n` tenths of a cell, in `SUBCELL`ths.
+pub const fn tenths(n: isize) -> isize {
+    n * SUBCELL / 10
+}
+
+/// How everything that flies or falls moves. Speeds are in `SUBCELL`ths of a
+/// cell per tick.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Physics {
+    /// Speed added to a body in the air each tick.
+    pub gravity: isize,
+    /// Fastest a body falls under gravity alone.
+    pub terminal_velocity: isize,
+    /// `SUBCELL`ths of its sideways speed a body loses each tick in the air.
+    pub air_friction: isize,
+    /// The same, for a body on a platform.
+    pub ground_friction: isize,
+    /// Upward speed a player's flap gives.
+    pub player_flap: isize,
+    /// Upward speed an enemy's flap gives.
+    pub enemy_flap: isize,
+}
+
+impl Default for Physics {
+    /// Riders coast without friction, as in the arcade game.
+    fn default() -> Self {
+        Self {
+            gravity: tenths(5),
+            terminal_velocity: tenths(30),
+            air_friction: 0,
+            ground_friction: 0,
+            player_flap: tenths(10),
+            enemy_flap: tenths(15),
+        }
+    }
+}
+
+/// Where something that flies or falls is, how fast it is going and whether
+/// it has its feet on a platform. Players, enemies and eggs all move through
+/// one of these, so gravity, landing and wrap-around work the same for each.
+///
+/// `x`, `y` is the top-left cell of a `width` by `height` sprite: the cell
+/// the body's true position falls in, `sub_x`, `sub_y` `SUBCELL`ths of a cell
+/// into it. Speeds are in `SUBCELL`ths of a cell per tick, so slow
+/// bodies still creep along. Columns wrap round the screen's width.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Body {
+    pub x: usize,
+    pub y: usize,
+    pub sub_x: isize,
+    pub sub_y: isize,
+    pub dx: isize,
+    pub dy: isize,
+    pub width: usize,
+    pub height: usize,
+    pub on_ground: bool,
+    pub dead: bool,
+}
+
+impl Body {
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self { x, y, sub_x: 0, sub_y: 0, dx: 0, dy: 0, width, height, on_ground: false, dead: false }
+    }
+
+    /// The box the body takes up, in `SUBCELL`ths of a cell.
+    pub fn aabb(&self) -> Aabb {
+        let (x, y) = (self.x as isize * SUBCELL + self.sub_x, self.y as isize * SUBCELL + self.sub_y);
+        Aabb { x1: x, y1: y, x2: x + self.width as isize * SUBCELL, y2: y + self.height as isize * SUBCELL }
+    }
+
+    /// Moves the body through the `quarter`th quarter of this tick, sweeping
+    /// it against `platforms` so that however fast it goes it can't pass
+    /// through one. It stops where it first touches a platform: landing on
+    /// top, bumping its head underneath or bouncing back off the side.
+    /// Leaving the top of the screen bounces it back down. Returns the
+    /// contact, if there was one.
+    pub fn advance(&mut self, quarter: isize, platforms: &[(usize, usize, usize, usize)]) -> Option<Contact> {
+        let motion = (
+            self.dx * quarter / 4 - self.dx * (quarter - 1) / 4,
+            self.dy * quarter / 4 - self.dy * (quarter - 1) / 4,
+        );
+        let start = self.aabb();
+        let hit = platforms
+            .iter()
+            .filter_map(|&platform| {
+                let platform = Aabb::cells(platform);
+                sweep(start, motion, platform).map(|contact| (contact, platform))
+            })
+            .min_by_key(|(contact, _)| contact.time);
+
+        let (mut fx, mut fy) = (start.x1 + motion.0, start.y1 + motion.1);
+        if let Some((contact, platform)) = hit {
+            let moved = Move { from: start, by: motion }.at(contact.time);
+            (fx, fy) = (moved.x1, moved.y1);
+            match contact.normal {
+                (0, -1) => {
+                    fy = platform.y1 - (start.y2 - start.y1);
+                    self.dy = self.dy.min(0);
+                }
+                (0, _) => {
+                    fy = platform.y2;
+                    self.dy = self.dy.max(0);
+                }
+                (-1, _) => {
+                    fx = platform.x1 - (start.x2 - start.x1);
+                    self.dx = -self.dx;
+                }
+                _ => {
+                    fx = platform.x2;
+                    self.dx = -self.dx;
+                }
+            }
+        }
+        if fy < 0 {
+            self.dy = -self.dy;
+            fy = 0;
+        }
+        let fx = fx.mod_floor(&(BUFFER_WIDTH as isize * SUBCELL));
+        (self.x, self.sub_x) = ((fx / SUBCELL) as usize, fx % SUBCELL);
+        (self.y, self.sub_y) = ((fy / SUBCELL) as usize, fy % SUBCELL);
+        self.land(platforms);
+        hit.map(|(contact, _)| contact)
+    }
+
+    /// Puts the body's top-left corner at the start of cell `x`, `y`.
+    pub fn place(&mut self, x: usize, y: usize) {
+        (self.x, self.y, self.sub_x, self.sub_y) = (x, y, 0, 0);
+    }
+
+    /// Whether the body has sunk off the bottom of the screen.
+    pub fn sunk(&self) -> bool {
+        self.y + self.height > BUFFER_HEIGHT
+    }
+
+    /// Whether the body at `sx`, `sy` would be standing on `platforms`:
+    /// there is platform under any of its columns, including columns that
+    /// wrap round to the other edge. Platforms are solid, so a body half
+    /// over the edge of one is held up by it.
+    pub fn stands_on(&self, sx: usize, sy: usize, platforms: &[(usize, usize, usize, usize)]) -> bool {
+        let feet = sy + self.height;
+        (0..self.width).any(|dx| {
+            let col = (sx + dx) % BUFFER_WIDTH;
+            platforms
+                .iter()
+                .any(|&(x1, y1, x2, y2)| feet >= y1 && feet < y2 && (x1..x2).contains(&col))
+        })
+    }
+
+    /// Notes whether the body is on `platforms`, stopping its fall if it is.
+    pub fn land(&mut self, platforms: &[(usize, usize, usize, usize)]) {
+        self.on_ground = self.stands_on(self.x, self.y, platforms);
+        if self.on_ground && self.dy > 0 {
+            self.dy = 0;
+        }
+    }
+
+    /// Applies a tick's gravity and friction.
+    pub fn fall(&mut self, physics: &Physics) {
+        if self.on_ground {
+            self.rub(physics.ground_friction);
+        } else {
+            self.rub(physics.air_friction);
+            if self.dy < physics.terminal_velocity {
+                self.dy = (self.dy + physics.gravity).min(physics.terminal_velocity);
+            }
+        }
+    }
+
+    /// Takes `friction` `SUBCELL`ths off the body's sideways speed.
+    pub fn rub(&mut self, friction: isize) {
+        self.dx -= self.dx * friction / SUBCELL;
+    }
+
+    /// Changes `dx` by `ddx`, but not past `limit` either way.
+    pub fn accelerate(&mut self, ddx: isize, limit: isize) {
+        self.dx = (self.dx + ddx).clamp(-limit, limit);
+    }
+
+    /// Erases the body's sprite.
+    pub fn clear(&self, screen: &mut impl Screen) {
+        for x in 0..self.width {
+            for y in 0..self.height {
+                screen.plot_wrapped(' ', self.x + x, self.y + y, ColorCode::new(Color::Black, Color::Black));
+            }
+        }
+    }
+}
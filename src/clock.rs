@@ -0,0 +1,63 @@
+/// Frequency of the PIT's input clock, in Hz.
+pub const PIT_HZ: u64 = 1_193_182;
+
+/// PIT divisor left in place by the BIOS, giving about 18.2 interrupts a second.
+pub const PIT_DEFAULT_DIVISOR: u64 = 65_536;
+
+/// Length of one simulation step, as the fraction of a second
+/// `STEP_PERIOD.0 / STEP_PERIOD.1`. All speeds, gravity and timers in the game
+/// are per step; they were tuned at the PIT's default rate, so that is the
+/// step rate.
+pub const STEP_PERIOD: (u64, u64) = (PIT_DEFAULT_DIVISOR, PIT_HZ);
+
+/// Most steps run for one batch of interrupts. Time beyond that is dropped so
+/// a long stall slows the game down instead of fast-forwarding it.
+pub const MAX_STEPS: usize = 8;
+
+/// Turns timer interrupts into a whole number of fixed-length simulation
+/// steps, carrying the leftover time to the next interrupt.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Clock {
+    /// Time added per interrupt, in units small enough that both periods are
+    /// whole numbers of them.
+    per_interrupt: u64,
+    /// Time one step takes, in the same units.
+    per_step: u64,
+    accumulator: u64,
+}
+
+impl Clock {
+    /// A clock for interrupts arriving `period.0 / period.1` seconds apart.
+    pub const fn new(period: (u64, u64)) -> Self {
+        Self {
+            per_interrupt: period.0 * STEP_PERIOD.1,
+            per_step: STEP_PERIOD.0 * period.1,
+            accumulator: 0,
+        }
+    }
+
+    /// A clock for interrupts arriving `hz` times a second.
+    pub const fn from_hz(hz: u64) -> Self {
+        Self::new((1, hz))
+    }
+
+    /// A clock for the PIT programmed with `divisor`.
+    pub const fn from_pit_divisor(divisor: u64) -> Self {
+        Self::new((divisor, PIT_HZ))
+    }
+
+    /// Accounts for `interrupts` more timer interrupts and returns how many
+    /// simulation steps are now due.
+    pub fn advance(&mut self, interrupts: usize) -> usize {
+        self.accumulator += self.per_interrupt * interrupts as u64;
+        let due = (self.accumulator / self.per_step) as usize;
+        self.accumulator %= self.per_step;
+        due.min(MAX_STEPS)
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::from_pit_divisor(PIT_DEFAULT_DIVISOR)
+    }
+}
@@ -0,0 +1,191 @@
+use core::fmt::{self, Write};
+
+use pc_keyboard::KeyCode;
+
+use crate::body::{tenths, Physics};
+use crate::enemy::EnemyType;
+
+/// The fewest and most lives a game can start with.
+pub const MIN_LIVES: usize = 1;
+pub const MAX_LIVES: usize = 9;
+
+/// How hard the enemies push.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// The fastest an enemy of type `etype` may fly sideways.
+    pub fn speed_cap(self, etype: EnemyType) -> isize {
+        let caps = match self {
+            Difficulty::Easy => [7, 18, 30],
+            Difficulty::Normal => [10, 25, 40],
+            Difficulty::Hard => [14, 32, 50],
+        };
+        tenths(match etype {
+            EnemyType::Bounder => caps[0],
+            EnemyType::Hunter => caps[1],
+            EnemyType::ShadowLord | EnemyType::Pterodactyl => caps[2],
+        })
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    pub fn prev(self) -> Self {
+        self.next().next()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
+/// Everything a key can be bound to.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Action {
+    Left1,
+    Right1,
+    Flap1,
+    Left2,
+    Right2,
+    Flap2,
+    /// Starts a game from the title or game over screen.
+    Start,
+    Pause,
+    Mute,
+}
+
+impl Action {
+    pub const ALL: [Action; 9] = [
+        Action::Left1,
+        Action::Right1,
+        Action::Flap1,
+        Action::Left2,
+        Action::Right2,
+        Action::Flap2,
+        Action::Start,
+        Action::Pause,
+        Action::Mute,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Left1 => "P1 left",
+            Action::Right1 => "P1 right",
+            Action::Flap1 => "P1 flap",
+            Action::Left2 => "P2 left",
+            Action::Right2 => "P2 right",
+            Action::Flap2 => "P2 flap",
+            Action::Start => "Start",
+            Action::Pause => "Pause",
+            Action::Mute => "Mute",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The settings a game is played with.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct GameConfig {
+    pub difficulty: Difficulty,
+    /// Lives each player starts with.
+    pub lives: usize,
+    /// Gravity, friction and flap strength for every body in the game.
+    pub physics: Physics,
+    /// The key bound to each action, in `Action::ALL` order.
+    keys: [KeyCode; Action::ALL.len()],
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            difficulty: Difficulty::Normal,
+            lives: 6,
+            physics: Physics::default(),
+            keys: [
+                KeyCode::ArrowLeft,
+                KeyCode::ArrowRight,
+                KeyCode::X,
+                KeyCode::A,
+                KeyCode::D,
+                KeyCode::W,
+                KeyCode::Z,
+                KeyCode::P,
+                KeyCode::M,
+            ],
+        }
+    }
+}
+
+impl GameConfig {
+    /// The key bound to `action`.
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.keys[action.index()]
+    }
+
+    /// The action `key` is bound to, if any.
+    pub fn action(&self, key: KeyCode) -> Option<Action> {
+        Action::ALL.into_iter().find(|&action| self.key(action) == key)
+    }
+
+    /// Binds `key` to `action`. An action already bound to `key` takes over
+    /// `action`'s old key, so no key ever does two things.
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        if let Some(other) = self.action(key) {
+            self.keys[other.index()] = self.key(action);
+        }
+        self.keys[action.index()] = key;
+    }
+
+    /// Adds `change` lives, staying within `MIN_LIVES..=MAX_LIVES`.
+    pub fn change_lives(&mut self, change: isize) {
+        self.lives = self.lives.saturating_add_signed(change).clamp(MIN_LIVES, MAX_LIVES);
+    }
+}
+
+/// The name of `key` for the settings screen, e.g. `ArrowLeft`.
+pub struct KeyName {
+    buf: [u8; 16],
+    len: usize,
+}
+
+impl KeyName {
+    pub fn new(key: KeyCode) -> Self {
+        let mut name = Self { buf: [0; 16], len: 0 };
+        let _ = write!(name, "{key:?}");
+        name
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("?")
+    }
+}
+
+impl Write for KeyName {
+    /// Keeps as much of `s` as fits, dropping the rest.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.len + c.len_utf8() > self.buf.len() {
+                break;
+            }
+            self.len += c.encode_utf8(&mut self.buf[self.len..]).len();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+use core::fmt::{self, Write};
+
+use crate::config::MAX_LIVES;
+use crate::screen::Screen;
+use crate::{Joust, State};
+
+/// Longest command line the console accepts; anything past it is dropped.
+pub const MAX_LINE: usize = 32;
+
+/// A debug console command, typed on its own line.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Command {
+    /// Print the players, enemies and eggs.
+    Dump,
+    /// Jump to wave N.
+    Wave(usize),
+    /// Give every player N lives, at most `MAX_LIVES`.
+    Lives(usize),
+    /// Toggle god mode.
+    God,
+    /// Freeze the game, then run one step.
+    Step,
+    /// Unfreeze the game.
+    Run,
+    Help,
+}
+
+/// Why a console line could not be run.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum CommandError {
+    Unknown,
+    /// The command needs a number and got none, or something else.
+    BadNumber,
+    /// The number is bigger than the command allows.
+    TooBig(usize),
+    NotPlaying,
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Unknown => write!(f, "unknown command; try help"),
+            CommandError::BadNumber => write!(f, "expected a number"),
+            CommandError::TooBig(most) => write!(f, "at most {most}"),
+            CommandError::NotPlaying => write!(f, "only during a game"),
+        }
+    }
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Result<Self, CommandError> {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some("dump") => Command::Dump,
+            Some("wave") => Command::Wave(number(words.next())?),
+            Some("lives") => match number(words.next())? {
+                lives if lives > MAX_LIVES => return Err(CommandError::TooBig(MAX_LIVES)),
+                lives => Command::Lives(lives),
+            },
+            Some("god") => Command::God,
+            Some("step") => Command::Step,
+            Some("run") => Command::Run,
+            Some("help") => Command::Help,
+            _ => return Err(CommandError::Unknown),
+        };
+        match words.next() {
+            Some(_) => Err(CommandError::Unknown),
+            None => Ok(command),
+        }
+    }
+}
+
+fn number(word: Option<&str>) -> Result<usize, CommandError> {
+    word.and_then(|w| w.parse().ok()).ok_or(CommandError::BadNumber)
+}
+
+/// Collects bytes from the serial port into command lines.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct LineBuffer {
+    line: [u8; MAX_LINE],
+    len: usize,
+}
+
+impl Default for LineBuffer {
+    fn default() -> Self {
+        Self {
+            line: [0; MAX_LINE],
+            len: 0,
+        }
+    }
+}
+
+impl LineBuffer {
+    /// Adds a byte, returning the line once `byte` ends one. Backspace and
+    /// delete remove the last character; empty lines are skipped.
+    pub fn push(&mut self, byte: u8) -> Option<&str> {
+        match byte {
+            b'\r' | b'\n' => {
+                let len = self.len;
+                self.len = 0;
+                core::str::from_utf8(&self.line[..len])
+                    .ok()
+                    .filter(|line| !line.trim().is_empty())
+            }
+            0x08 | 0x7f => {
+                self.len = self.len.saturating_sub(1);
+                None
+            }
+            _ => {
+                if self.len < MAX_LINE {
+                    self.line[self.len] = byte;
+                    self.len += 1;
+                }
+                None
+            }
+        }
+    }
+}
+
+impl<S: Screen> Joust<S> {
+    /// Runs a debug console line, writing any reply to `out`. Console
+    /// commands are not recorded, so a replay of a game that used them will
+    /// drift.
+    pub fn command(&mut self, line: &str, out: &mut impl Write) -> fmt::Result {
+        match Command::parse(line).and_then(|command| self.run_command(command, out)) {
+            Ok(result) => result,
+            Err(e) => writeln!(out, "error: {e}"),
+        }
+    }
+
+    fn run_command(
+        &mut self,
+        command: Command,
+        out: &mut impl Write,
+    ) -> Result<fmt::Result, CommandError> {
+        if matches!(command, Command::Wave(_) | Command::Lives(_)) && self.state != State::Playing {
+            return Err(CommandError::NotPlaying);
+        }
+        Ok(match command {
+            Command::Dump => self.dump(out),
+            Command::Wave(wave) => {
+                self.start_wave(wave.max(1));
+                writeln!(out, "wave {}", self.wave)
+            }
+            Command::Lives(lives) => {
+                for player in self.players.iter_mut().take(self.mode.players()) {
+                    player.lives = lives;
+                }
+                writeln!(out, "lives {lives}")
+            }
+            Command::God => {
+                self.god = !self.god;
+                for player in self.players.iter_mut() {
+                    player.god = self.god;
+                }
+                writeln!(out, "god {}", if self.god { "on" } else { "off" })
+            }
+            Command::Step => {
+                self.frozen = true;
+                self.single_steps += 1;
+                writeln!(out, "frozen; running to step {}", self.steps as usize + self.single_steps)
+            }
+            Command::Run => {
+                self.frozen = false;
+                self.single_steps = 0;
+                writeln!(out, "running")
+            }
+            Command::Help => writeln!(out, "commands: dump, wave N, lives N, god, step, run"),
+        })
+    }
+
+    fn dump(&self, out: &mut impl Write) -> fmt::Result {
+        writeln!(
+            out,
+            "{:?} {:?} wave {} step {} spawned {} wave ticks {}",
+            self.state, self.mode, self.wave, self.steps, self.spawned_enemies, self.wave_ticks
+        )?;
+        for (p, player) in self.players().enumerate() {
+            writeln!(
+                out,
+                "P{} at ({}, {}) moving ({}, {}) score {} lives {}{}{}",
+                p + 1,
+                player.body.x,
+                player.body.y,
+                player.body.dx,
+                player.body.dy,
+                player.score,
+                player.lives,
+                if player.body.dead { " dead" } else { "" },
+                if player.out { " out" } else { "" },
+            )?;
+        }
+        for (i, enemy) in self.enemies.iter().enumerate().filter(|(_, e)| !e.body.dead) {
+            writeln!(
+                out,
+                "enemy {i} {:?} at ({}, {}) moving ({}, {})",
+                enemy.etype, enemy.body.x, enemy.body.y, enemy.body.dx, enemy.body.dy
+            )?;
+        }
+        for (i, egg) in self.eggs.iter().enumerate().filter(|(_, e)| !e.body.dead) {
+            writeln!(
+                out,
+                "egg {i} {:?} at ({}, {}) hatches in {}",
+                egg.etype, egg.body.x, egg.body.y, egg.hatch_timer
+            )?;
+        }
+        Ok(())
+    }
+}
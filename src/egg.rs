@@ -0,0 +1,88 @@
+use crate::body::{Body, Physics, SUBCELL};
+use crate::enemy::EnemyType;
+use crate::screen::{Color, ColorCode, Screen, BUFFER_WIDTH};
+
+/// Ticks an egg must rest on a platform before it hatches.
+pub const HATCH_TICKS: usize = 90;
+
+/// Points for catching an egg before it hatches.
+pub const EGG_BONUS: usize = 250;
+
+/// `SUBCELL`ths of its sideways speed an egg loses each tick it rolls along
+/// a platform.
+const EGG_FRICTION: isize = SUBCELL / 2;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Egg {
+    pub body: Body,
+    /// The rider that climbs out when this egg hatches.
+    pub etype: EnemyType,
+    pub hatch_timer: usize,
+}
+
+impl Default for Egg {
+    fn default() -> Self {
+        Self {
+            body: Body { dead: true, ..Body::new(0, 0, 1, 1) },
+            etype: EnemyType::Bounder,
+            hatch_timer: HATCH_TICKS,
+        }
+    }
+}
+
+impl Egg {
+    /// The egg left behind by a defeated rider of type `defeated` whose
+    /// sprite was at `x`, `y` moving with `dx`, `dy`.
+    pub fn dropped_by(defeated: EnemyType, x: usize, y: usize, dx: isize, dy: isize) -> Self {
+        Self {
+            body: Body {
+                dx: dx / 2,
+                dy,
+                ..Body::new((x + 1) % BUFFER_WIDTH, y + 2, 1, 1)
+            },
+            etype: defeated.next_tier(),
+            ..Self::default()
+        }
+    }
+
+    pub fn clear(&self, screen: &mut impl Screen) {
+        self.body.clear(screen);
+    }
+
+    /// Moves the egg one tick, falling with the same gravity as the riders.
+    /// An egg that falls into the lava is destroyed.
+    pub fn update(&mut self, ground_bounding_boxes: &[(usize, usize, usize, usize)], physics: &Physics) {
+        for quarter in 1..5 {
+            self.body.advance(quarter, ground_bounding_boxes);
+            if self.body.sunk() {
+                self.body.dead = true;
+                return;
+            }
+        }
+
+        if self.body.on_ground {
+            self.body.rub(EGG_FRICTION);
+            self.hatch_timer = self.hatch_timer.saturating_sub(1);
+        } else {
+            self.body.fall(physics);
+        }
+    }
+
+    pub fn ready_to_hatch(&self) -> bool {
+        !self.body.dead && self.hatch_timer == 0
+    }
+
+    pub fn draw(&self, screen: &mut impl Screen) {
+        let shell = if self.hatch_timer < HATCH_TICKS / 4 && self.hatch_timer.is_multiple_of(2) {
+            'O'
+        } else {
+            'o'
+        };
+        screen.plot_wrapped(
+            shell,
+            self.body.x,
+            self.body.y,
+            ColorCode::new(Color::White, Color::Black),
+        );
+    }
+}
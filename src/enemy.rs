@@ -1,293 +1,350 @@
 
-use num::Integer;
-use pluggable_interrupt_os::vga_buffer::{plot, Color, ColorCode, BUFFER_HEIGHT, BUFFER_WIDTH};
+use crate::body::{tenths, Body, Physics};
+use crate::config::GameConfig;
+use crate::level::Level;
+use crate::rider::{self, Rider};
+use crate::wrapped_distance;
+use crate::screen::{Color, ColorCode, Screen, BUFFER_HEIGHT, BUFFER_WIDTH};
 
-const MOVE_WIDTH: isize = BUFFER_WIDTH as isize - 4;
+/// Horizontal speed of a pterodactyl swoop, in the same units as `dx`.
+const SWOOP_SPEED: isize = tenths(40);
+/// Ticks a pterodactyl holds its line before picking a new one.
+const SWOOP_TICKS: usize = 16;
+/// Rows from the top below which a rider with no platform beneath it flaps to
+/// keep out of the lava.
+const LOW_ROW: usize = 16;
+/// Ticks a rider sticks with a plan before rethinking it.
+const PLAN_TICKS: usize = 24;
+/// Columns within which a Bounder may give up patrolling to attack.
+const NOTICE_RANGE: usize = 16;
+/// Rows a Hunter wants above the player before it dives.
+const CLIMB_MARGIN: usize = 2;
+/// Columns within which a ShadowLord that isn't above the player breaks off.
+const FEINT_RANGE: usize = 10;
+/// Ticks a ShadowLord spends backing off after a feint.
+const RETREAT_TICKS: usize = 12;
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 
 pub enum EnemyType {
     Bounder,
     Hunter,
     ShadowLord,
+    /// Shows up when a wave drags on. It can only be killed by a lance to the mouth.
+    Pterodactyl,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+impl EnemyType {
+    /// The tougher rider that hatches from an egg dropped by this one.
+    pub fn next_tier(self) -> Self {
+        match self {
+            EnemyType::Bounder => EnemyType::Hunter,
+            EnemyType::Hunter | EnemyType::ShadowLord | EnemyType::Pterodactyl => {
+                EnemyType::ShadowLord
+            }
+        }
+    }
+}
+
+/// What a rider is currently trying to do. Each type moves between plans
+/// its own way in `Enemy::think`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Plan {
+    /// Fly to the platform `Enemy::goal` and walk it, turning at the edges.
+    Patrol,
+    /// Gain height over the player before attacking.
+    Climb,
+    /// Go straight for the player.
+    Attack,
+    /// Back off and gain height after a feint.
+    Retreat,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Enemy {
-    pub x: usize,
-    pub y: usize,
-    pub dx: isize,
-    pub dy: isize,
+    pub body: Body,
     pub etype: EnemyType,
     pub score: usize,
     pub lives: usize,
-    pub on_ground: bool,
-    pub dead: bool,
+    /// Ticks left in the current pterodactyl swoop.
+    pub swoop: usize,
+    pub plan: Plan,
+    /// Ticks left before the current plan is rethought.
+    pub plan_ticks: usize,
+    /// Which way the rider wants to go: -1 left, 1 right, 0 neither.
+    pub heading: isize,
+    /// Index into the level's platforms of the one being patrolled.
+    pub goal: usize,
+    /// State of the rider's dice, so each rider's choices are its own but
+    /// replays still come out the same.
+    pub seed: u32,
+    /// Ticks left materialising on a spawn point, unable to move or joust.
+    pub spawning: usize,
+    /// Ticks left recoiling from a tied joust, unable to joust again.
+    pub recoil: usize,
 }
 
 impl Default for Enemy {
     fn default() -> Self {
         Self {
-            x: BUFFER_WIDTH / 2,
-            y: BUFFER_HEIGHT / 2,
+            body: Body {
+                dead: true,
+                ..Rider::body(BUFFER_WIDTH / 2, BUFFER_HEIGHT / 2)
+            },
             score: 0,
             lives: 6,
             etype: EnemyType::Bounder,
-            dx: 0,
-            dy: 0,
-            on_ground: false,
-            dead: true,
+            swoop: 0,
+            plan: Plan::Patrol,
+            plan_ticks: 0,
+            heading: 1,
+            goal: 0,
+            seed: 0x9e37_79b9,
+            spawning: 0,
+            recoil: 0,
         }
     }
 }
 
 impl Enemy {
-    pub fn clear(&self) {
-        for x in 0..4 {
-            for y in 0..3 {
-                plot(
-                    ' ',
-                    x + self.x,
-                    y + self.y,
-                    ColorCode::new(Color::Black, Color::Black),
-                )
-            }
-        }
+    pub fn clear(&self, screen: &mut impl Screen) {
+        self.body.clear(screen);
     }
 
-    fn is_on_ground(
-        &self,
-        sx: usize,
-        sy: usize,
-        ground_bounding_boxes: [(usize, usize, usize, usize); 5],
-    ) -> bool {
-        for (x1, y1, x2, y2) in ground_bounding_boxes {
-            if sy + 3 >= y1 && sy + 3 < y2 && sx >= x1 && sx + 3 < x2 {
-                return true;
+    /// Moves the enemy through the `quarter`th quarter of this tick,
+    /// returning where it ends up, or `None` if it sank into the lava.
+    /// Pterodactyls fly straight through platforms.
+    pub fn update_quarter_step(&mut self, quarter: isize, ground_bounding_boxes: &[(usize, usize, usize, usize)]) -> Option<(isize, isize)> {
+        if self.etype == EnemyType::Pterodactyl {
+            self.body.advance(quarter, &[]);
+        } else {
+            self.body.advance(quarter, ground_bounding_boxes);
+        }
+        if self.body.sunk() {
+            if self.etype != EnemyType::Pterodactyl {
+                self.die();
+                return None
             }
+            self.body.dy = -self.body.dy;
+            self.body.place(self.body.x, BUFFER_HEIGHT - rider::HEIGHT);
         }
-        return false;
+        Some((self.body.x as isize, self.body.y as isize))
     }
 
-    pub fn update_quarter_step(&mut self, quarter: isize, ground_bounding_boxes: [(usize, usize, usize, usize); 5]) -> Option<(isize, isize)> {
-        let sx = (self.x as isize + (self.dx * quarter / 40)).mod_floor(&MOVE_WIDTH);
-        let mut sy = self.y as isize + (self.dy * quarter / 40);
+    /// The cell holding the pterodactyl's open mouth when its sprite is at `x`, `y`.
+    pub fn mouth_at(&self, x: isize, y: isize) -> (isize, isize) {
+        if self.body.dx < 0 {
+            (x, y + 1)
+        } else {
+            (x + 3, y + 1)
+        }
+    }
 
-        if sy >= 23 {
-            if self.die() {
-                self.dead = true;
-                return None
-            }
-        } else if sy < 0 {
-            self.dy = -self.dy;
-            sy = 0
+    fn draw_pterodactyl(&self, screen: &mut impl Screen) {
+        const BODY_COLOR: Color = Color::Magenta;
+        let body = ColorCode::new(BODY_COLOR, Color::Black);
+        let (tail_x, mouth_x, mouth) = if self.body.dx < 0 {
+            (self.body.x + 3, self.body.x, '<')
+        } else {
+            (self.body.x, self.body.x + 3, '>')
+        };
+        screen.plot_wrapped(mouth, mouth_x, self.body.y + 1, ColorCode::new(Color::LightRed, Color::Black));
+        screen.plot_wrapped(254u8 as char, self.body.x + 1, self.body.y + 1, body);
+        screen.plot_wrapped(254u8 as char, self.body.x + 2, self.body.y + 1, body);
+        screen.plot_wrapped(196u8 as char, tail_x, self.body.y + 1, body);
+        if (self.body.x / 2).is_multiple_of(2) {
+            screen.plot_wrapped('\\', self.body.x + 1, self.body.y, body);
+            screen.plot_wrapped('/', self.body.x + 2, self.body.y, body);
+        } else {
+            screen.plot_wrapped('/', self.body.x + 1, self.body.y + 2, body);
+            screen.plot_wrapped('\\', self.body.x + 2, self.body.y + 2, body);
         }
-        
-        self.on_ground =  self.is_on_ground(sx as usize, sy as usize, ground_bounding_boxes);
+    }
 
-        if self.on_ground && self.dy > 0 {
-            self.dy = 0
+    /// How the rider looks; the bird is the same for every type.
+    pub fn rider(&self) -> Rider {
+        let rider = match self.etype {
+            EnemyType::Hunter => Color::LightGray,
+            EnemyType::ShadowLord => Color::Blue,
+            _ => Color::Red,
+        };
+        Rider { rider, bird: Color::Green }
+    }
+
+    pub fn draw(&self, screen: &mut impl Screen) {
+        if self.etype == EnemyType::Pterodactyl {
+            return self.draw_pterodactyl(screen);
         }
-        Some((sx, sy))
+        self.rider().draw(screen, &self.body);
+    }
 
+    pub fn die(&mut self) {
+        self.body.dead = true;
     }
 
-    pub fn draw(&self) {
-        const BIRD_COLOR: Color = Color::Green;
-        let mut rider_color: Color = Color::Red;
-        if self.etype == EnemyType::Hunter {
-            rider_color = Color::LightGray;
-        } else if self.etype == EnemyType::ShadowLord {
-            rider_color = Color::Blue;
+    /// Decides where to go this tick, no faster than `config`'s difficulty
+    /// allows. Bounders patrol platforms and only sometimes attack, Hunters
+    /// climb above the player before diving, and ShadowLords feint and back
+    /// off unless they already have height. None of them fly low over lava.
+    pub fn think(&mut self, player_x: usize, player_y: usize, level: &Level, config: &GameConfig) {
+        if self.etype == EnemyType::Pterodactyl {
+            self.swoop_toward(player_x, player_y);
+            return;
         }
-        if self.dx < 0 {
-            plot(
-                '_',
-                self.x,
-                self.y,
-                ColorCode::new(Color::White, Color::Black),
-            );
-            plot(
-                184u8 as char,
-                self.x + 1,
-                self.y,
-                ColorCode::new(Color::Brown, Color::Black),
-            );
-            plot(
-                2u8 as char,
-                self.x + 2,
-                self.y,
-                ColorCode::new(rider_color, Color::Black),
-            );
-            plot(
-                254u8 as char,
-                self.x + 1,
-                self.y + 1,
-                ColorCode::new(BIRD_COLOR, Color::Black),
-            );
-            plot(
-                254u8 as char,
-                self.x + 2,
-                self.y + 1,
-                ColorCode::new(BIRD_COLOR, Color::Black),
-            );
-            plot(
-                16u8 as char,
-                self.x + 3,
-                self.y + 1,
-                ColorCode::new(BIRD_COLOR, Color::Black),
-            );
-            if self.on_ground {
-                plot(
-                    '|' as char,
-                    self.x + 1,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
-                plot(
-                    '\\' as char,
-                    self.x + 2,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
-            } else {
-                plot(
-                    14u8 as char,
-                    self.x + 1,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
+        self.plan_ticks = self.plan_ticks.saturating_sub(1);
+        let row = match self.etype {
+            EnemyType::Bounder => self.bounder(player_x, player_y, level),
+            EnemyType::Hunter => self.hunter(player_x, player_y),
+            _ => self.shadow_lord(player_x, player_y),
+        };
+        if self.plan == Plan::Attack && self.body.on_ground && player_y > self.body.y {
+            // Walk off the nearer edge rather than pace above the player.
+            if let Some(i) = self.platform_below(level) {
+                let (x1, _, x2, _) = level.platforms()[i];
+                self.heading = if self.body.x - x1 < x2.saturating_sub(self.body.x + self.body.width) { -1 } else { 1 };
             }
-        } else {
-            plot(
-                '_',
-                self.x + 3,
-                self.y,
-                ColorCode::new(Color::White, Color::Black),
-            );
-            plot(
-                213u8 as char,
-                self.x + 2,
-                self.y,
-                ColorCode::new(Color::Brown, Color::Black),
-            );
-            plot(
-                2u8 as char,
-                self.x + 1,
-                self.y,
-                ColorCode::new(rider_color, Color::Black),
-            );
-            plot(
-                254u8 as char,
-                self.x + 2,
-                self.y + 1,
-                ColorCode::new(BIRD_COLOR, Color::Black),
-            );
-            plot(
-                254u8 as char,
-                self.x + 1,
-                self.y + 1,
-                ColorCode::new(BIRD_COLOR, Color::Black),
-            );
-            plot(
-                17u8 as char,
-                self.x,
-                self.y + 1,
-                ColorCode::new(BIRD_COLOR, Color::Black),
-            );
-            if self.on_ground {
-                plot(
-                    '|' as char,
-                    self.x + 2,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
-                plot(
-                    '/' as char,
-                    self.x + 1,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
+        }
+        let cap = config.difficulty.speed_cap(self.etype);
+        match self.heading {
+            -1 => self.accel_left(cap),
+            1 => self.accel_right(cap),
+            _ => (),
+        }
+        self.body.dx = self.body.dx.clamp(-cap, cap);
+        if self.body.y > row || self.in_danger(level) {
+            self.flap(&config.physics);
+        }
+    }
+
+    /// Whether the rider is low down with nothing under it to land on.
+    pub fn in_danger(&self, level: &Level) -> bool {
+        self.body.y > LOW_ROW && self.platform_below(level).is_none()
+    }
+
+    /// The index of the nearest platform the rider would land on if it
+    /// dropped straight down.
+    fn platform_below(&self, level: &Level) -> Option<usize> {
+        level
+            .platforms()
+            .iter()
+            .enumerate()
+            .filter(|(_, &(x1, y1, x2, _))| {
+                y1 >= self.body.y + self.body.height && (0..self.body.width).all(|dx| (x1..x2).contains(&((self.body.x + dx) % BUFFER_WIDTH)))
+            })
+            .min_by_key(|(_, &(_, y1, _, _))| y1)
+            .map(|(i, _)| i)
+    }
+
+    /// Sets the heading and returns the row to climb to. A Bounder patrols a
+    /// platform, now and then flying off to another or going for a nearby
+    /// player.
+    fn bounder(&mut self, player_x: usize, player_y: usize, level: &Level) -> usize {
+        if self.plan_ticks == 0 {
+            self.plan = if wrapped_distance(self.body.x, player_x) < NOTICE_RANGE && self.roll(2) == 0 {
+                Plan::Attack
             } else {
-                plot(
-                    14u8 as char,
-                    self.x + 2,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
+                if self.roll(3) == 0 || self.goal >= level.platforms().len() {
+                    self.goal = self.roll(level.platforms().len() as u32) as usize;
+                }
+                Plan::Patrol
+            };
+            self.plan_ticks = PLAN_TICKS;
+        }
+        if self.plan == Plan::Attack {
+            self.heading = self.toward(player_x);
+            return player_y;
+        }
+        let (x1, y1, x2, _) = level.platforms()[self.goal];
+        if self.body.on_ground && self.platform_below(level) == Some(self.goal) {
+            if self.body.x <= x1 + 1 {
+                self.heading = 1;
+            } else if self.body.x + 5 >= x2 {
+                self.heading = -1;
             }
+        } else {
+            self.heading = self.toward(((x1 + x2) / 2).saturating_sub(2));
         }
+        y1.saturating_sub(3)
     }
 
-    pub fn die(&mut self) -> bool {
-        true
+    /// Sets the heading and returns the row to climb to. A Hunter climbs until
+    /// it is well above the player, then dives.
+    fn hunter(&mut self, player_x: usize, player_y: usize) -> usize {
+        let above = if self.plan == Plan::Attack {
+            self.body.y <= player_y
+        } else {
+            self.body.y + CLIMB_MARGIN <= player_y
+        };
+        self.plan = if above { Plan::Attack } else { Plan::Climb };
+        self.heading = self.toward(player_x);
+        if above {
+            player_y
+        } else {
+            player_y.saturating_sub(CLIMB_MARGIN + 1)
+        }
     }
 
-    pub fn think(&mut self, player_x: usize, player_y: usize) {
-        if self.y > 18 || self.y > player_y {
-            self.flap();
+    /// Sets the heading and returns the row to climb to. A ShadowLord goes
+    /// for the player but breaks off close in unless it is already above.
+    fn shadow_lord(&mut self, player_x: usize, player_y: usize) -> usize {
+        if self.plan == Plan::Retreat && self.plan_ticks > 0 {
+            self.heading = -self.toward(player_x);
+            return player_y.saturating_sub(CLIMB_MARGIN + 2);
         }
-        match self.etype {
-            EnemyType::Bounder => {
-                if self.x > player_x {
-                    self.accel_left(10);
-                } else if self.x < player_x {
-                    self.accel_right(10);
-                }
-                if self.dx > 10 {
-                    self.dx = 10;
-                } else if self.dx < -10 {
-                    self.dx = -10
-                }
-            },
-            EnemyType::Hunter => {
-                if self.x > player_x  {
-                    self.accel_left(25);
-                } else if self.x < player_x {
-                    self.accel_right(25);
-                }
-                if self.dx > 25 {
-                    self.dx = 25;
-                } else if self.dx < -25 {
-                    self.dx = -25
-                }
-            },
-            EnemyType::ShadowLord => {
-                if self.x > player_x {
-                    self.accel_left(40);
-                } else if self.x < player_x {
-                    self.accel_right(40);
-                }
-                if self.dx > 40 {
-                    self.dx = 40;
-                } else if self.dx < -40 {
-                    self.dx = -40
-                }
-            },
+        self.plan = Plan::Attack;
+        self.heading = self.toward(player_x);
+        if wrapped_distance(self.body.x, player_x) < FEINT_RANGE && self.body.y >= player_y {
+            self.plan = Plan::Retreat;
+            self.plan_ticks = RETREAT_TICKS;
         }
+        player_y
     }
 
-    fn accel_left(&mut self, speed_limit: isize) {
-        if self.dx < speed_limit {
-            if self.on_ground {
-                self.dx = self.dx - 3
-            } else {
-                self.dx = self.dx - 2;
-            }
+    /// Which way round the screen is the shorter way to column `x`.
+    fn toward(&self, x: usize) -> isize {
+        let ahead = (x + BUFFER_WIDTH - self.body.x % BUFFER_WIDTH) % BUFFER_WIDTH;
+        if ahead == 0 {
+            0
+        } else if ahead <= BUFFER_WIDTH / 2 {
+            1
+        } else {
+            -1
         }
     }
 
-    fn accel_right(&mut self, speed_limit: isize) {
-        if self.dx < speed_limit {
-            if self.on_ground {
-                self.dx = self.dx + 3
-            } else {
-                self.dx = self.dx + 2;
-            }
+    /// A number below `n`, which must not be 0.
+    fn roll(&mut self, n: u32) -> u32 {
+        let mut s = self.seed ^ ((self.body.x as u32) << 8 | self.body.y as u32) | 1;
+        s ^= s << 13;
+        s ^= s >> 17;
+        s ^= s << 5;
+        self.seed = s;
+        s % n
+    }
+
+    /// Lines the pterodactyl up on a straight dive at the player and holds it
+    /// for `SWOOP_TICKS` before taking aim again.
+    fn swoop_toward(&mut self, player_x: usize, player_y: usize) {
+        if self.swoop > 0 {
+            self.swoop -= 1;
+            return;
         }
+        let run = wrapped_distance(self.body.x, player_x).max(1) as isize;
+        let rise = player_y as isize - self.body.y as isize;
+        self.body.dx = if self.toward(player_x) < 0 { -SWOOP_SPEED } else { SWOOP_SPEED };
+        self.body.dy = (rise * SWOOP_SPEED / run).clamp(-SWOOP_SPEED, SWOOP_SPEED);
+        self.swoop = SWOOP_TICKS;
+    }
+
+    fn accel_left(&mut self, speed_limit: isize) {
+        self.body.accelerate(if self.body.on_ground { -tenths(3) } else { -tenths(2) }, speed_limit);
+    }
+
+    fn accel_right(&mut self, speed_limit: isize) {
+        self.body.accelerate(if self.body.on_ground { tenths(3) } else { tenths(2) }, speed_limit);
     }
 
-    fn flap(&mut self) {
-        self.dy = -15
+    fn flap(&mut self, physics: &Physics) {
+        self.body.dy = -physics.enemy_flap
     }
 }
@@ -0,0 +1,105 @@
+use core::fmt;
+
+use crate::enemy::EnemyType;
+use crate::troll::Victim;
+use crate::State;
+
+/// Events queued before the oldest unread one is dropped.
+pub const MAX_EVENTS: usize = 32;
+
+/// Something that happened in the game, for whoever is listening. Players are
+/// numbered from 0.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Event {
+    StateChanged(State),
+    WaveStarted(usize),
+    PterodactylArrived,
+    EggHatched(EnemyType),
+    /// A player was unhorsed and has `lives` left.
+    PlayerDied { player: usize, lives: usize },
+    /// A player lost their last life.
+    PlayerOut(usize),
+    EnemyDefeated { player: usize, etype: EnemyType, points: usize },
+    EggCollected { player: usize, points: usize },
+    /// A player got through a survival wave without losing a life.
+    WaveBonus { player: usize, points: usize },
+    /// In versus, `winner` unhorsed `loser`.
+    PlayerDefeated { winner: usize, loser: usize, points: usize },
+    /// The lava troll's hand took hold of a rider.
+    TrollGrabbed(Victim),
+    /// A rider flapped free of the lava troll's hand.
+    BrokeFree(Victim),
+    /// A player's score went into the high-score table at `rank`, from 0.
+    HighScoreAdded { player: usize, rank: usize },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Event::StateChanged(state) => write!(f, "state: {state:?}"),
+            Event::WaveStarted(wave) => write!(f, "wave {wave}"),
+            Event::PterodactylArrived => write!(f, "pterodactyl arrived"),
+            Event::EggHatched(etype) => write!(f, "egg hatched a {etype:?}"),
+            Event::PlayerDied { player, lives } => {
+                write!(f, "P{} died, {lives} lives left", player + 1)
+            }
+            Event::PlayerOut(player) => write!(f, "P{} is out", player + 1),
+            Event::EnemyDefeated { player, etype, points } => {
+                write!(f, "P{} beat a {etype:?} (+{points})", player + 1)
+            }
+            Event::EggCollected { player, points } => {
+                write!(f, "P{} caught an egg (+{points})", player + 1)
+            }
+            Event::WaveBonus { player, points } => {
+                write!(f, "P{} survived the wave (+{points})", player + 1)
+            }
+            Event::PlayerDefeated { winner, loser, points } => {
+                write!(f, "P{} beat P{} (+{points})", winner + 1, loser + 1)
+            }
+            Event::TrollGrabbed(victim) => write!(f, "troll grabbed {victim}"),
+            Event::BrokeFree(victim) => write!(f, "{victim} broke free of the troll"),
+            Event::HighScoreAdded { player, rank } => {
+                write!(f, "P{} took high score #{}", player + 1, rank + 1)
+            }
+        }
+    }
+}
+
+/// A first-in, first-out queue of events. When it is full the oldest event
+/// makes way for the newest.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Events {
+    queue: [Option<Event>; MAX_EVENTS],
+    head: usize,
+    len: usize,
+}
+
+impl Default for Events {
+    fn default() -> Self {
+        Self {
+            queue: [None; MAX_EVENTS],
+            head: 0,
+            len: 0,
+        }
+    }
+}
+
+impl Events {
+    pub fn push(&mut self, event: Event) {
+        if self.len == MAX_EVENTS {
+            self.pop();
+        }
+        self.queue[(self.head + self.len) % MAX_EVENTS] = Some(event);
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<Event> {
+        if self.len == 0 {
+            return None;
+        }
+        let event = self.queue[self.head].take();
+        self.head = (self.head + 1) % MAX_EVENTS;
+        self.len -= 1;
+        event
+    }
+}
@@ -0,0 +1,151 @@
+use core::hash::Hasher;
+
+use crate::replay::Fnv;
+
+/// Entries in the high-score table.
+pub const TABLE_SIZE: usize = 10;
+
+/// Bytes in a disk sector, the unit the table is saved in.
+pub const SECTOR_SIZE: usize = 512;
+
+/// Marks a sector holding a saved table, and the layout version.
+const MAGIC: [u8; 4] = *b"JHS1";
+/// Bytes per saved entry: three initials and a little-endian `u32` score.
+const ENTRY_SIZE: usize = 7;
+const CHECKSUM_AT: usize = MAGIC.len() + TABLE_SIZE * ENTRY_SIZE;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Entry {
+    pub initials: [u8; 3],
+    pub score: usize,
+}
+
+/// The best ten scores, highest first.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct HighScores {
+    entries: [Entry; TABLE_SIZE],
+}
+
+impl Default for HighScores {
+    fn default() -> Self {
+        const INITIALS: [&[u8; 3]; TABLE_SIZE] =
+            [b"JST", b"EGG", b"LAV", b"PTR", b"BND", b"HNT", b"SHD", b"OST", b"BZD", b"KNT"];
+        let mut entries = [Entry { initials: [b' '; 3], score: 0 }; TABLE_SIZE];
+        for (i, entry) in entries.iter_mut().enumerate() {
+            *entry = Entry {
+                initials: *INITIALS[i],
+                score: (TABLE_SIZE - i) * 1000,
+            };
+        }
+        Self { entries }
+    }
+}
+
+impl HighScores {
+    /// A table of `entries`, sorted highest first.
+    pub fn new(mut entries: [Entry; TABLE_SIZE]) -> Self {
+        entries.sort_unstable_by_key(|entry| core::cmp::Reverse(entry.score));
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Whether `score` would make it into the table.
+    pub fn qualifies(&self, score: usize) -> bool {
+        score > self.entries[TABLE_SIZE - 1].score
+    }
+
+    /// Adds `score` under `initials`, bumping the lowest entry off the table.
+    /// Returns the new entry's rank from 0, or `None` if it didn't qualify.
+    pub fn insert(&mut self, initials: [u8; 3], score: usize) -> Option<usize> {
+        if !self.qualifies(score) {
+            return None;
+        }
+        let rank = self.entries.iter().position(|entry| score > entry.score)?;
+        self.entries.copy_within(rank..TABLE_SIZE - 1, rank + 1);
+        self.entries[rank] = Entry { initials, score };
+        Some(rank)
+    }
+
+    /// The table as a disk sector: a magic number, the entries, then a
+    /// checksum of everything before it.
+    pub fn to_sector(&self) -> [u8; SECTOR_SIZE] {
+        let mut sector = [0; SECTOR_SIZE];
+        sector[..MAGIC.len()].copy_from_slice(&MAGIC);
+        for (i, entry) in self.entries.iter().enumerate() {
+            let at = MAGIC.len() + i * ENTRY_SIZE;
+            let score = u32::try_from(entry.score).unwrap_or(u32::MAX);
+            sector[at..at + 3].copy_from_slice(&entry.initials);
+            sector[at + 3..at + ENTRY_SIZE].copy_from_slice(&score.to_le_bytes());
+        }
+        let checksum = checksum(&sector[..CHECKSUM_AT]);
+        sector[CHECKSUM_AT..CHECKSUM_AT + 8].copy_from_slice(&checksum.to_le_bytes());
+        sector
+    }
+
+    /// Reads a table written by `to_sector`, or `None` if the sector holds
+    /// anything else, including a table that has been corrupted.
+    pub fn from_sector(sector: &[u8; SECTOR_SIZE]) -> Option<Self> {
+        let mut stored = [0; 8];
+        stored.copy_from_slice(&sector[CHECKSUM_AT..CHECKSUM_AT + 8]);
+        if sector[..MAGIC.len()] != MAGIC
+            || u64::from_le_bytes(stored) != checksum(&sector[..CHECKSUM_AT])
+        {
+            return None;
+        }
+        let mut table = Self::default();
+        for (i, entry) in table.entries.iter_mut().enumerate() {
+            let at = MAGIC.len() + i * ENTRY_SIZE;
+            let mut score = [0; 4];
+            score.copy_from_slice(&sector[at + 3..at + ENTRY_SIZE]);
+            entry.initials.copy_from_slice(&sector[at..at + 3]);
+            entry.score = u32::from_le_bytes(score) as usize;
+        }
+        Some(table)
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u64 {
+    let mut hasher = Fnv::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Initials being typed in by a player who made the table.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct InitialsEntry {
+    /// The player typing, from 0.
+    pub player: usize,
+    pub initials: [u8; 3],
+    pub len: usize,
+}
+
+impl InitialsEntry {
+    pub fn new(player: usize) -> Self {
+        Self {
+            player,
+            initials: [b'_'; 3],
+            len: 0,
+        }
+    }
+
+    /// Handles a typed character: letters fill the initials, backspace takes
+    /// one back. Returns true when enter confirms all three.
+    pub fn key(&mut self, c: char) -> bool {
+        match c {
+            'a'..='z' | 'A'..='Z' if self.len < 3 => {
+                self.initials[self.len] = c.to_ascii_uppercase() as u8;
+                self.len += 1;
+            }
+            '\u{8}' if self.len > 0 => {
+                self.len -= 1;
+                self.initials[self.len] = b'_';
+            }
+            '\n' | '\r' => return self.len == 3,
+            _ => {}
+        }
+        false
+    }
+}
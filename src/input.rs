@@ -0,0 +1,95 @@
+use pc_keyboard::{DecodedKey, KeyCode, KeyState};
+
+/// Keyboard input as the game receives it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum KeyInput {
+    /// A key going down or up, passed to `Joust::key_event`.
+    Event(KeyCode, KeyState),
+    /// A typed key, passed to `Joust::key`.
+    Typed(DecodedKey),
+}
+
+/// A set of key codes, one bit per code.
+#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
+struct KeySet([u128; 2]);
+
+impl KeySet {
+    fn bit(code: KeyCode) -> (usize, u128) {
+        let code = code as u8;
+        ((code / 128) as usize, 1 << (code % 128))
+    }
+
+    fn insert(&mut self, code: KeyCode) {
+        let (word, bit) = Self::bit(code);
+        self.0[word] |= bit;
+    }
+
+    fn remove(&mut self, code: KeyCode) {
+        let (word, bit) = Self::bit(code);
+        self.0[word] &= !bit;
+    }
+
+    fn contains(&self, code: KeyCode) -> bool {
+        let (word, bit) = Self::bit(code);
+        self.0[word] & bit != 0
+    }
+}
+
+/// Which keys are down, built up from key press and release events rather
+/// than the keyboard's typematic repeat.
+///
+/// Presses are remembered until the step that sees them ends, so a key tapped
+/// and released between two steps still counts for one.
+#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
+pub struct Input {
+    down: KeySet,
+    pressed: KeySet,
+}
+
+impl Input {
+    /// Applies `code` going down or up. Returns true if it is a fresh press;
+    /// repeats of a key already down and releases return false.
+    pub fn update(&mut self, code: KeyCode, state: KeyState) -> bool {
+        match state {
+            KeyState::Down if self.down.contains(code) => false,
+            KeyState::Down => {
+                self.down.insert(code);
+                self.pressed.insert(code);
+                true
+            }
+            KeyState::SingleShot => {
+                self.pressed.insert(code);
+                true
+            }
+            KeyState::Up => {
+                self.down.remove(code);
+                false
+            }
+        }
+    }
+
+    /// Whether `code` going down or up would change anything: keyboard
+    /// repeats of a key already down, and releases of keys that aren't, don't.
+    pub fn changes(&self, code: KeyCode, state: KeyState) -> bool {
+        match state {
+            KeyState::Down => !self.down.contains(code),
+            KeyState::SingleShot => true,
+            KeyState::Up => self.down.contains(code),
+        }
+    }
+
+    /// Whether `code` is down, or was pressed since the last step.
+    pub fn is_held(&self, code: KeyCode) -> bool {
+        self.down.contains(code) || self.pressed.contains(code)
+    }
+
+    /// Whether `code` went down since the last step.
+    pub fn just_pressed(&self, code: KeyCode) -> bool {
+        self.pressed.contains(code)
+    }
+
+    /// Forgets presses once a step has seen them.
+    pub fn end_step(&mut self) {
+        self.pressed = KeySet::default();
+    }
+}
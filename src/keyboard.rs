@@ -0,0 +1,36 @@
+use crossbeam::atomic::AtomicCell;
+use pluggable_interrupt_os::interrupts::PICS;
+use x86_64::instructions::{interrupts, port::Port, tables::sidt};
+use x86_64::structures::idt::{InterruptDescriptorTable, InterruptStackFrame};
+
+/// The vector pluggable_interrupt_os maps the keyboard's IRQ 1 to.
+const KEYBOARD_VECTOR: u8 = 33;
+
+static HANDLER: AtomicCell<Option<fn(u8)>> = AtomicCell::new(None);
+
+/// A copy of the OS's interrupt table with our own keyboard handler.
+static mut IDT: InterruptDescriptorTable = InterruptDescriptorTable::new();
+
+/// Takes over the keyboard interrupt so `handler` sees every raw scancode,
+/// releases included, rather than only the decoded key presses the
+/// `HandlerTable` passes on. Call it once the OS has loaded its interrupt
+/// table, i.e. from `cpu_loop`.
+pub fn install(handler: fn(u8)) {
+    HANDLER.store(Some(handler));
+    interrupts::without_interrupts(|| unsafe {
+        let idt = &mut *core::ptr::addr_of_mut!(IDT);
+        *idt = (*sidt().base.as_ptr::<InterruptDescriptorTable>()).clone();
+        idt[KEYBOARD_VECTOR].set_handler_fn(keyboard_interrupt);
+        idt.load();
+    });
+}
+
+extern "x86-interrupt" fn keyboard_interrupt(_stack_frame: InterruptStackFrame) {
+    let scancode: u8 = unsafe { Port::new(0x60).read() };
+    if let Some(handler) = HANDLER.load() {
+        handler(scancode);
+    }
+    unsafe {
+        PICS.lock().notify_end_of_interrupt(KEYBOARD_VECTOR);
+    }
+}
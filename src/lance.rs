@@ -0,0 +1,64 @@
+use crate::body::{tenths, Body};
+use crate::screen::BUFFER_WIDTH;
+
+/// Sideways speed two riders are knocked apart at when neither wins.
+pub const KNOCKBACK: isize = tenths(20);
+
+/// Ticks after being knocked apart during which a rider can't joust, so the
+/// same pair doesn't meet again before they have separated.
+pub const RECOIL_TICKS: usize = 8;
+
+/// What a rider brings to a joust.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Lance {
+    /// The row the lance is drawn on, along the top of the sprite.
+    pub row: isize,
+    /// The rider's vertical speed: below 0 when flapping upward.
+    pub dy: isize,
+}
+
+impl Lance {
+    /// The lance of a rider whose sprite is at row `y`, moving as `body` is.
+    pub fn at(y: isize, body: &Body) -> Self {
+        Self { row: y, dy: body.dy }
+    }
+}
+
+/// Which of two riders comes out on top.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Outcome {
+    First,
+    Second,
+    /// Neither: the riders are knocked apart.
+    Tie,
+}
+
+/// The higher lance wins. With lances level, a rider flapping upward beats
+/// one that is falling; otherwise it's a tie.
+pub fn resolve(first: Lance, second: Lance) -> Outcome {
+    if first.row != second.row {
+        return if first.row < second.row { Outcome::First } else { Outcome::Second };
+    }
+    if first.dy < 0 && second.dy > 0 {
+        Outcome::First
+    } else if second.dy < 0 && first.dy > 0 {
+        Outcome::Second
+    } else {
+        Outcome::Tie
+    }
+}
+
+/// Sends two riders that tied off at `KNOCKBACK` speed, each away from the
+/// other. Riders in the same column part the way they were going.
+pub fn knock_apart(first: &mut Body, second: &mut Body) {
+    let ahead = (second.x + BUFFER_WIDTH - first.x % BUFFER_WIDTH) % BUFFER_WIDTH;
+    let away = if ahead == 0 {
+        if first.dx > second.dx { 1 } else { -1 }
+    } else if ahead <= BUFFER_WIDTH / 2 {
+        -1
+    } else {
+        1
+    };
+    first.dx = away * KNOCKBACK;
+    second.dx = -away * KNOCKBACK;
+}
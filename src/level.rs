@@ -0,0 +1,337 @@
+use core::fmt;
+
+use crate::screen::{BUFFER_HEIGHT, BUFFER_WIDTH};
+
+/// Most platforms a level may have.
+pub const MAX_PLATFORMS: usize = 12;
+/// Most lava pits a level may have.
+pub const MAX_LAVA: usize = 6;
+/// Most spawn points a level may have.
+pub const MAX_SPAWN_POINTS: usize = 8;
+
+/// Waves played on a level before moving on to the next one.
+pub const WAVES_PER_LEVEL: usize = 3;
+
+/// The layout of one arena: where riders can stand, where the lava is, and
+/// where riders appear. Both collision and drawing are derived from it.
+///
+/// Platforms are `(x1, y1, x2, y2)` boxes with exclusive right and bottom
+/// edges, lava pits are `(x1, y, x2)` spans, and spawn points and player
+/// starts are the top-left corner of a rider standing there.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Level {
+    platforms: [(usize, usize, usize, usize); MAX_PLATFORMS],
+    platform_count: usize,
+    lava: [(usize, usize, usize); MAX_LAVA],
+    lava_count: usize,
+    spawn_points: [(usize, usize); MAX_SPAWN_POINTS],
+    spawn_count: usize,
+    player_starts: [(usize, usize); 2],
+}
+
+impl Level {
+    /// Builds a level from its parts. Panics (at compile time for `const`
+    /// levels) if there are more parts than a level can hold.
+    pub const fn new(
+        platforms: &[(usize, usize, usize, usize)],
+        lava: &[(usize, usize, usize)],
+        spawn_points: &[(usize, usize)],
+        player_starts: [(usize, usize); 2],
+    ) -> Self {
+        assert!(platforms.len() <= MAX_PLATFORMS);
+        assert!(lava.len() <= MAX_LAVA);
+        assert!(spawn_points.len() <= MAX_SPAWN_POINTS);
+
+        let mut level = Self {
+            platforms: [(0, 0, 0, 0); MAX_PLATFORMS],
+            platform_count: platforms.len(),
+            lava: [(0, 0, 0); MAX_LAVA],
+            lava_count: lava.len(),
+            spawn_points: [(0, 0); MAX_SPAWN_POINTS],
+            spawn_count: spawn_points.len(),
+            player_starts,
+        };
+        let mut i = 0;
+        while i < platforms.len() {
+            level.platforms[i] = platforms[i];
+            i += 1;
+        }
+        i = 0;
+        while i < lava.len() {
+            level.lava[i] = lava[i];
+            i += 1;
+        }
+        i = 0;
+        while i < spawn_points.len() {
+            level.spawn_points[i] = spawn_points[i];
+            i += 1;
+        }
+        level
+    }
+
+    /// Parses an ASCII map of the screen. `#` is platform, `~` is lava, `S` is
+    /// an enemy spawn point and `P` a player start (first player first, in
+    /// reading order); spaces and `.` are open air. `S` and `P` mark the
+    /// top-left corner of a rider standing there. Lines may be shorter than
+    /// the screen is wide.
+    ///
+    /// Spawn points are used in reading order unless numbered: `1` to `9` are
+    /// spawn points taken in that order, ahead of any `S`. `a` to `i` are the
+    /// same numbered spawn points on a cell that is also the next player start.
+    ///
+    /// Each horizontal run of `#` is a platform, merged with the run directly
+    /// above it when both span the same columns. Each run of `~` is a lava pit.
+    pub const fn parse(map: &str) -> Result<Self, ParseError> {
+        let bytes = map.as_bytes();
+        let mut level = Self::new(&[], &[], &[], [(0, 0); 2]);
+        let mut starts = 0;
+        let (mut x, mut y) = (0, 0);
+        // Where the run of platform or lava under way began, if any.
+        let mut platform_run: Option<usize> = None;
+        let mut lava_run: Option<usize> = None;
+        let mut numbered: [Option<(usize, usize)>; 9] = [None; 9];
+        let mut i = 0;
+        while i <= bytes.len() {
+            let b = if i < bytes.len() { bytes[i] } else { b'\n' };
+            i += 1;
+            if b == b'\r' {
+                continue;
+            }
+
+            if b != b'#' {
+                if let Some(x1) = platform_run {
+                    if !level.add_platform(x1, y, x) {
+                        return Err(ParseError::at(x1, y, ParseErrorKind::TooManyPlatforms));
+                    }
+                    platform_run = None;
+                }
+            }
+            if b != b'~' {
+                if let Some(x1) = lava_run {
+                    if level.lava_count == MAX_LAVA {
+                        return Err(ParseError::at(x1, y, ParseErrorKind::TooManyLava));
+                    }
+                    level.lava[level.lava_count] = (x1, y, x);
+                    level.lava_count += 1;
+                    lava_run = None;
+                }
+            }
+            if b == b'\n' {
+                x = 0;
+                y += 1;
+                continue;
+            }
+
+            if y >= BUFFER_HEIGHT {
+                return Err(ParseError::at(x, y, ParseErrorKind::TooManyLines));
+            }
+            if x >= BUFFER_WIDTH {
+                return Err(ParseError::at(x, y, ParseErrorKind::LineTooLong));
+            }
+            match b {
+                b' ' | b'.' => {}
+                b'#' => {
+                    if platform_run.is_none() {
+                        platform_run = Some(x);
+                    }
+                }
+                b'~' => {
+                    if lava_run.is_none() {
+                        lava_run = Some(x);
+                    }
+                }
+                b'S' => {
+                    if level.spawn_count == MAX_SPAWN_POINTS {
+                        return Err(ParseError::at(x, y, ParseErrorKind::TooManySpawnPoints));
+                    }
+                    level.spawn_points[level.spawn_count] = (x, y);
+                    level.spawn_count += 1;
+                }
+                b'1'..=b'9' | b'a'..=b'i' => {
+                    let n = (if b <= b'9' { b - b'1' } else { b - b'a' }) as usize;
+                    if numbered[n].is_some() {
+                        return Err(ParseError::at(x, y, ParseErrorKind::DuplicateSpawnPoint(b)));
+                    }
+                    numbered[n] = Some((x, y));
+                }
+                b'P' => {}
+                _ => return Err(ParseError::at(x, y, ParseErrorKind::UnknownTile(b))),
+            }
+            if b == b'P' || b.is_ascii_lowercase() {
+                if starts == 2 {
+                    return Err(ParseError::at(x, y, ParseErrorKind::TooManyPlayerStarts));
+                }
+                level.player_starts[starts] = (x, y);
+                starts += 1;
+            }
+            x += 1;
+        }
+
+        if starts < 2 {
+            return Err(ParseError::at(0, y, ParseErrorKind::MissingPlayerStart));
+        }
+        if !level.number_spawn_points(&numbered) {
+            return Err(ParseError::at(0, y, ParseErrorKind::TooManySpawnPoints));
+        }
+        if level.spawn_count == 0 {
+            return Err(ParseError::at(0, y, ParseErrorKind::NoSpawnPoints));
+        }
+        Ok(level)
+    }
+
+    /// Puts the `numbered` spawn points, in order, ahead of those found in
+    /// reading order. Returns false if there are too many in all.
+    const fn number_spawn_points(&mut self, numbered: &[Option<(usize, usize)>]) -> bool {
+        let mut count = 0;
+        let mut i = 0;
+        while i < numbered.len() {
+            if numbered[i].is_some() {
+                count += 1;
+            }
+            i += 1;
+        }
+        if self.spawn_count + count > MAX_SPAWN_POINTS {
+            return false;
+        }
+        i = self.spawn_count;
+        while i > 0 {
+            i -= 1;
+            self.spawn_points[i + count] = self.spawn_points[i];
+        }
+        self.spawn_count += count;
+        let mut next = 0;
+        i = 0;
+        while i < numbered.len() {
+            if let Some(point) = numbered[i] {
+                self.spawn_points[next] = point;
+                next += 1;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Adds the platform run from `x1` to `x2` on row `y`, stretching a
+    /// platform on the row above that spans the same columns. Returns false
+    /// if the level is already full.
+    const fn add_platform(&mut self, x1: usize, y: usize, x2: usize) -> bool {
+        let mut i = 0;
+        while i < self.platform_count {
+            let (px1, _, px2, py2) = self.platforms[i];
+            if px1 == x1 && px2 == x2 && py2 == y {
+                self.platforms[i].3 = y + 1;
+                return true;
+            }
+            i += 1;
+        }
+        if self.platform_count == MAX_PLATFORMS {
+            return false;
+        }
+        self.platforms[self.platform_count] = (x1, y, x2, y + 1);
+        self.platform_count += 1;
+        true
+    }
+
+    /// The level played during wave `wave`, counting from 1.
+    pub fn for_wave(wave: usize) -> Self {
+        LEVELS[(wave.max(1) - 1) / WAVES_PER_LEVEL % LEVELS.len()]
+    }
+
+    pub fn platforms(&self) -> &[(usize, usize, usize, usize)] {
+        &self.platforms[..self.platform_count]
+    }
+
+    pub fn lava(&self) -> &[(usize, usize, usize)] {
+        &self.lava[..self.lava_count]
+    }
+
+    pub fn spawn_points(&self) -> &[(usize, usize)] {
+        &self.spawn_points[..self.spawn_count]
+    }
+
+    /// Where the first and second players start a game.
+    pub fn player_starts(&self) -> [(usize, usize); 2] {
+        self.player_starts
+    }
+}
+
+/// The levels in the order they are played.
+pub const LEVELS: [Level; 3] = [
+    // The original arena.
+    level(include_str!("levels/arena.txt")),
+    // Ledges stepping up to a high center bridge.
+    level(include_str!("levels/bridge.txt")),
+    // Two high perches over a narrow middle step.
+    level(include_str!("levels/perches.txt")),
+];
+
+/// Parses a map built into the kernel, failing the build if it is malformed.
+const fn level(map: &str) -> Level {
+    match Level::parse(map) {
+        Ok(level) => level,
+        Err(_) => panic!("malformed level map; run the host tests for the line and column"),
+    }
+}
+
+/// What is wrong with a level map.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ParseErrorKind {
+    /// A byte that is not one of the map tiles.
+    UnknownTile(u8),
+    /// A line wider than the screen.
+    LineTooLong,
+    /// More lines than the screen is tall.
+    TooManyLines,
+    TooManyPlatforms,
+    TooManyLava,
+    TooManySpawnPoints,
+    /// A spawn point number used twice.
+    DuplicateSpawnPoint(u8),
+    /// A third `P`.
+    TooManyPlayerStarts,
+    /// Fewer than two `P`s; reported just past the end of the map.
+    MissingPlayerStart,
+    /// No spawn point anywhere; reported just past the end of the map.
+    NoSpawnPoints,
+}
+
+/// A malformed level map, pointing at the offending character. Lines and
+/// columns count from 1.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// An error at the zero-based column `x` of row `y`.
+    const fn at(x: usize, y: usize, kind: ParseErrorKind) -> Self {
+        Self { line: y + 1, column: x + 1, kind }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match self.kind {
+            ParseErrorKind::UnknownTile(b) if b.is_ascii_graphic() => {
+                write!(f, "unknown tile '{}'", b as char)
+            }
+            ParseErrorKind::UnknownTile(b) => write!(f, "unknown tile byte {b:#04x}"),
+            ParseErrorKind::LineTooLong => write!(f, "line is wider than {BUFFER_WIDTH} columns"),
+            ParseErrorKind::TooManyLines => write!(f, "map is taller than {BUFFER_HEIGHT} lines"),
+            ParseErrorKind::TooManyPlatforms => write!(f, "more than {MAX_PLATFORMS} platforms"),
+            ParseErrorKind::TooManyLava => write!(f, "more than {MAX_LAVA} lava pits"),
+            ParseErrorKind::TooManySpawnPoints => {
+                write!(f, "more than {MAX_SPAWN_POINTS} spawn points")
+            }
+            ParseErrorKind::DuplicateSpawnPoint(b) => {
+                write!(f, "spawn point '{}' used twice", b as char)
+            }
+            ParseErrorKind::TooManyPlayerStarts => write!(f, "more than two player starts"),
+            ParseErrorKind::MissingPlayerStart => write!(f, "fewer than two player starts"),
+            ParseErrorKind::NoSpawnPoints => write!(f, "no spawn points"),
+        }
+    }
+}
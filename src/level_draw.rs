@@ -1,65 +1,109 @@
 use num::Integer;
-use pluggable_interrupt_os::vga_buffer::{plot, Color, ColorCode};
+use crate::config::{GameConfig, KeyName, MAX_LIVES};
+use crate::high_score::{HighScores, InitialsEntry};
+use crate::level::Level;
+use crate::pause::{PauseItem, PauseMenu, MENU_HEIGHT, MENU_WIDTH, MENU_X, MENU_Y};
+use crate::screen::{Color, ColorCode, Screen, BUFFER_WIDTH};
+use crate::settings::{self, SettingsItem, SettingsMenu};
+use crate::wave::Rule;
+use crate::SPAWN_TICKS;
 
-pub fn draw_platforms() {
-    for x in 0..15 {
-        plot(' ', x, 12, ColorCode::new(Color::Brown, Color::Brown));
+pub fn draw_platforms(screen: &mut impl Screen, level: &Level) {
+    for &(x1, y1, x2, y2) in level.platforms() {
+        for x in x1..x2 {
+            for y in y1..y2 {
+                screen.plot(' ', x, y, ColorCode::new(Color::Brown, Color::Brown));
+            }
+        }
     }
-    for x in 70..80 {
-        plot(' ', x, 12, ColorCode::new(Color::Brown, Color::Brown));
-    }
-    for x in 60..70 {
-        plot(' ', x, 11, ColorCode::new(Color::Brown, Color::Brown));
+}
+
+pub fn draw_lava(screen: &mut impl Screen, level: &Level) {
+    for &(x1, y, x2) in level.lava() {
+        for x in x1..x2 {
+            screen.plot(
+                178u8 as char,
+                x,
+                y,
+                ColorCode::new(Color::Red, Color::Yellow),
+            );
+        }
     }
-    for x in 20..60 {
-        plot(' ', x, 23, ColorCode::new(Color::Brown, Color::Brown));
+}
+
+pub fn draw_ui(screen: &mut impl Screen, score: usize, lives: usize, wave: usize) {
+    for x in 21..59 {
+        screen.plot(' ', x, 24, ColorCode::new(Color::Brown, Color::Brown))
     }
-    for x in 35..50 {
-        plot(' ', x, 7, ColorCode::new(Color::Brown, Color::Brown));
+    draw_score(screen, 23, 24, score, Color::Brown);
+    draw_lives(screen, 35, 24, lives, Color::Yellow);
+    draw_wave(screen, 45, 24, wave);
+}
+
+/// Draws the second player's score and lives in a bar along the top row.
+pub fn draw_player_two_ui(screen: &mut impl Screen, score: usize, lives: usize) {
+    for x in 21..59 {
+        screen.plot(' ', x, 0, ColorCode::new(Color::Brown, Color::Brown))
     }
+    draw_score(screen, 23, 0, score, Color::Brown);
+    draw_lives(screen, 35, 0, lives, Color::LightCyan);
+    draw_text(screen, "P2", 45, 0, ColorCode::new(Color::LightCyan, Color::Brown));
 }
 
-pub fn draw_lava() {
-    for x in 0..21 {
-        plot(
-            178u8 as char,
-            x,
-            24,
-            ColorCode::new(Color::Red, Color::Yellow),
-        );
+/// Draws a heart per life from `sx`, or one heart and the count when there
+/// are more than `MAX_LIVES`, so the row never runs into the wave number.
+fn draw_lives(screen: &mut impl Screen, sx: usize, sy: usize, lives: usize, color: Color) {
+    let color = ColorCode::new(color, Color::Brown);
+    if lives <= MAX_LIVES {
+        for x in 0..lives {
+            screen.plot(1u8 as char, sx + x, sy, color);
+        }
+        return;
     }
-    for x in 59..80 {
-        plot(
-            178u8 as char,
-            x,
-            24,
-            ColorCode::new(Color::Red, Color::Yellow),
-        );
+    screen.plot(1u8 as char, sx, sy, color);
+    screen.plot('x', sx + 1, sy, color);
+    // Room for seven digits before the wave number.
+    let lives = lives.min(9_999_999);
+    let digits = lives.ilog10() as usize + 1;
+    for i in 0..digits {
+        let digit = lives / 10_usize.pow((digits - 1 - i) as u32) % 10;
+        screen.plot((b'0' + digit as u8) as char, sx + 2 + i, sy, color);
     }
 }
 
-pub fn draw_ui(score: usize, lives: usize, wave: usize) {
-    for x in 21..59 {
-        plot(' ', x, 24, ColorCode::new(Color::Brown, Color::Brown))
+pub fn draw_text(screen: &mut impl Screen, text: &str, sx: usize, sy: usize, color: ColorCode) {
+    for (x, c) in text.chars().enumerate() {
+        screen.plot(c, sx + x, sy, color);
     }
-    draw_score(23, 24, score, Color::Brown);
-    for x in 0..lives {
-        plot(
-            1u8 as char,
-            x + 35,
-            24,
-            ColorCode::new(Color::Yellow, Color::Brown),
-        );
+}
+
+/// Draws the 1P / 2P co-op / 2P versus choice on the title screen, with
+/// option `selected` highlighted.
+pub fn draw_mode_select(screen: &mut impl Screen, selected: usize) {
+    const MODE_Y: usize = 17;
+    const MODES: [(&str, usize); 3] = [("1 Player", 22), ("2P Co-op", 35), ("2P Versus", 48)];
+
+    screen.plot(17u8 as char, 19, MODE_Y, ColorCode::new(Color::LightBlue, Color::Black));
+    screen.plot(16u8 as char, 59, MODE_Y, ColorCode::new(Color::LightBlue, Color::Black));
+    for (i, (name, x)) in MODES.iter().enumerate() {
+        let color = if i == selected {
+            ColorCode::new(Color::White, Color::LightBlue)
+        } else {
+            ColorCode::new(Color::LightGray, Color::Black)
+        };
+        draw_text(screen, name, *x, MODE_Y, color);
     }
-    draw_wave(45, 24, wave);
+    draw_text(screen, "Press", 30, 22, ColorCode::new(Color::DarkGray, Color::Black));
+    draw_text(screen, "S", 36, 22, ColorCode::new(Color::White, Color::DarkGray));
+    draw_text(screen, "for settings", 38, 22, ColorCode::new(Color::DarkGray, Color::Black));
 }
 
-pub fn draw_score(sx: usize, sy: usize, score: usize, back_color: Color) {
+pub fn draw_score(screen: &mut impl Screen, sx: usize, sy: usize, score: usize, back_color: Color) {
     for x in 0usize..9 {
         let d = 10_usize.pow(9 - x as u32);
         let v = 10_usize.pow(8 - x as u32);
         let num = score.mod_floor(&d) / v + 48;
-        plot(
+        screen.plot(
             num as u8 as char,
             x + sx,
             sy,
@@ -68,17 +112,17 @@ pub fn draw_score(sx: usize, sy: usize, score: usize, back_color: Color) {
     }
 }
 
-pub fn draw_wave(sx: usize, sy: usize, wave: usize) {
-    plot('W', sx, sy, ColorCode::new(Color::Yellow, Color::Brown));
-    plot('a', sx+1, sy, ColorCode::new(Color::Yellow, Color::Brown));
-    plot('v', sx+2, sy, ColorCode::new(Color::Yellow, Color::Brown));
-    plot('e', sx+3, sy, ColorCode::new(Color::Yellow, Color::Brown));
-    plot(':', sx+4, sy, ColorCode::new(Color::Yellow, Color::Brown));
+pub fn draw_wave(screen: &mut impl Screen, sx: usize, sy: usize, wave: usize) {
+    screen.plot('W', sx, sy, ColorCode::new(Color::Yellow, Color::Brown));
+    screen.plot('a', sx+1, sy, ColorCode::new(Color::Yellow, Color::Brown));
+    screen.plot('v', sx+2, sy, ColorCode::new(Color::Yellow, Color::Brown));
+    screen.plot('e', sx+3, sy, ColorCode::new(Color::Yellow, Color::Brown));
+    screen.plot(':', sx+4, sy, ColorCode::new(Color::Yellow, Color::Brown));
     for x in 0usize..2 {
         let d = 10_usize.pow(2 - x as u32);
         let v = 10_usize.pow(1 - x as u32);
         let num = wave.mod_floor(&d) / v + 48;
-        plot(
+        screen.plot(
             num as u8 as char,
             x + sx + 6,
             sy,
@@ -87,13 +131,13 @@ pub fn draw_wave(sx: usize, sy: usize, wave: usize) {
     }
 }
 
-pub fn draw_titlescreen() {
+pub fn draw_titlescreen(screen: &mut impl Screen) {
     const TITLE_X: usize = 34;
     const TITLE_Y: usize = 10;
 
     // for x in 0..80 {
     //     for y in 6..17 {
-    //         plot(
+    //         screen.plot(
     //             ' ',
     //             x,
     //             y,
@@ -102,7 +146,7 @@ pub fn draw_titlescreen() {
     //     }
     // }
     for x in 0..80 {
-        plot(
+        screen.plot(
             205u8 as char,
             x,
             11,
@@ -110,7 +154,7 @@ pub fn draw_titlescreen() {
         );
     }
     for y in 9..14 {
-        plot(
+        screen.plot(
             186u8 as char,
             39,
             y,
@@ -118,86 +162,86 @@ pub fn draw_titlescreen() {
         );
     }
     // for y in 9..14 {
-    //     plot(
+    //     screen.plot(
     //         186u8 as char,
     //         39,
     //         y,
     //         ColorCode::new(Color::Blue, Color::Red),
     //     );
     // }
-    plot(
+    screen.plot(
         179u8 as char,
         39,
         8,
         ColorCode::new(Color::LightBlue, Color::Black),
     );
-    plot(
+    screen.plot(
         179u8 as char,
         39,
         14,
         ColorCode::new(Color::LightBlue, Color::Black),
     );
-    plot(
+    screen.plot(
         179u8 as char,
         39,
         7,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         179u8 as char,
         39,
         15,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         15u8 as char,
         39,
         11,
         ColorCode::new(Color::LightBlue, Color::Black),
     );
-    plot(
+    screen.plot(
         '/',
         37,
         13,
         ColorCode::new(Color::LightBlue, Color::Black),
     );
-    plot(
+    screen.plot(
         '/',
         41,
         9,
         ColorCode::new(Color::LightBlue, Color::Black),
     );
-    plot(
+    screen.plot(
         '\\',
         41,
         13,
         ColorCode::new(Color::LightBlue, Color::Black),
     );
-    plot(
+    screen.plot(
         '\\',
         37,
         9,
         ColorCode::new(Color::LightBlue, Color::Black),
     );
-    plot(
+    screen.plot(
         '/',
         36,
         14,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         '/',
         42,
         8,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         '\\',
         42,
         14,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         '\\',
         36,
         8,
@@ -205,31 +249,31 @@ pub fn draw_titlescreen() {
     );
 
     // J
-    plot(
+    screen.plot(
         205u8 as char,
         TITLE_X,
         TITLE_Y,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         187u8 as char,
         TITLE_X + 1,
         TITLE_Y,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         186u8 as char,
         TITLE_X + 1,
         TITLE_Y + 1,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         200u8 as char,
         TITLE_X,
         TITLE_Y + 2,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         188u8 as char,
         TITLE_X + 1,
         TITLE_Y + 2,
@@ -237,37 +281,37 @@ pub fn draw_titlescreen() {
     );
 
     // O
-    plot(
+    screen.plot(
         201u8 as char,
         TITLE_X + 2,
         TITLE_Y,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         187u8 as char,
         TITLE_X + 3,
         TITLE_Y,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         186u8 as char,
         TITLE_X + 2,
         TITLE_Y + 1,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         186u8 as char,
         TITLE_X + 3,
         TITLE_Y + 1,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         200u8 as char,
         TITLE_X + 2,
         TITLE_Y + 2,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         188u8 as char,
         TITLE_X + 3,
         TITLE_Y + 2,
@@ -275,42 +319,42 @@ pub fn draw_titlescreen() {
     );
 
     // U
-    plot(
+    screen.plot(
         186u8 as char,
         TITLE_X + 4,
         TITLE_Y,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         186u8 as char,
         TITLE_X + 6,
         TITLE_Y,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         186u8 as char,
         TITLE_X + 4,
         TITLE_Y + 1,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         186u8 as char,
         TITLE_X + 6,
         TITLE_Y + 1,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         200u8 as char,
         TITLE_X + 4,
         TITLE_Y + 2,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(205u8 as char, 
+    screen.plot(205u8 as char, 
         TITLE_X + 5, 
         TITLE_Y + 2,
         ColorCode::new(Color::Yellow, Color::Red)
     );
-    plot(
+    screen.plot(
         188u8 as char,
         TITLE_X + 6,
         TITLE_Y + 2,
@@ -318,37 +362,37 @@ pub fn draw_titlescreen() {
     );
 
     // S
-    plot(
+    screen.plot(
         201u8 as char,
         TITLE_X + 7,
         TITLE_Y,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         205u8 as char,
         TITLE_X + 8,
         TITLE_Y,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         200u8 as char,
         TITLE_X + 7,
         TITLE_Y + 1,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         187u8 as char,
         TITLE_X + 8,
         TITLE_Y + 1,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         205u8 as char,
         TITLE_X + 7,
         TITLE_Y + 2,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         188u8 as char,
         TITLE_X + 8,
         TITLE_Y + 2,
@@ -356,25 +400,25 @@ pub fn draw_titlescreen() {
     );
 
     // T
-    plot(
+    screen.plot(
         201u8 as char,
         TITLE_X + 9,
         TITLE_Y,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         205u8 as char,
         TITLE_X + 10,
         TITLE_Y,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         186u8 as char,
         TITLE_X + 9,
         TITLE_Y + 1,
         ColorCode::new(Color::Yellow, Color::Red),
     );
-    plot(
+    screen.plot(
         211u8 as char,
         TITLE_X + 9,
         TITLE_Y + 2,
@@ -387,79 +431,79 @@ pub fn draw_titlescreen() {
     const PROMPT_X: usize = 32;
     const PROMPT_Y: usize = 20;
 
-    plot(
+    screen.plot(
         'P',
         PROMPT_X,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         'r',
         PROMPT_X+1,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         'e',
         PROMPT_X+2,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         's',
         PROMPT_X+3,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         's',
         PROMPT_X+4,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         'Z',
         PROMPT_X+6,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::LightBlue),
     );
-    plot(
+    screen.plot(
         't',
         PROMPT_X+8,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         'o',
         PROMPT_X+9,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         's',
         PROMPT_X+11,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         't',
         PROMPT_X+12,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         'a',
         PROMPT_X+13,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         'r',
         PROMPT_X+14,
         PROMPT_Y,
         ColorCode::new(Color::White, Color::Black),
     );
-    plot(
+    screen.plot(
         't',
         PROMPT_X+15,
         PROMPT_Y,
@@ -468,59 +512,59 @@ pub fn draw_titlescreen() {
 
 }
 
-pub fn draw_game_over(player_score: usize) {
+pub fn draw_game_over(screen: &mut impl Screen, player_score: usize, second_score: Option<usize>) {
     const GAME_OVER_X: usize = 35;
     const GAME_OVER_Y: usize = 10;
     // Game Over
-    plot(
+    screen.plot(
         'G',
         GAME_OVER_X,
         GAME_OVER_Y,
         ColorCode::new(Color::Red, Color::Black),
     );
-    plot(
+    screen.plot(
         'A',
         GAME_OVER_X+1,
         GAME_OVER_Y,
         ColorCode::new(Color::Red, Color::Black),
     );
-    plot(
+    screen.plot(
         'M',
         GAME_OVER_X+2,
         GAME_OVER_Y,
         ColorCode::new(Color::Red, Color::Black),
     );
-    plot(
+    screen.plot(
         'E',
         GAME_OVER_X+3,
         GAME_OVER_Y,
         ColorCode::new(Color::Red, Color::Black),
     );
-    plot(
+    screen.plot(
         'O',
         GAME_OVER_X+5,
         GAME_OVER_Y,
         ColorCode::new(Color::Red, Color::Black),
     );
-    plot(
+    screen.plot(
         'V',
         GAME_OVER_X+6,
         GAME_OVER_Y,
         ColorCode::new(Color::Red, Color::Black),
     );
-    plot(
+    screen.plot(
         'E',
         GAME_OVER_X+7,
         GAME_OVER_Y,
         ColorCode::new(Color::Red, Color::Black),
     );
-    plot(
+    screen.plot(
         'R',
         GAME_OVER_X+8,
         GAME_OVER_Y,
         ColorCode::new(Color::Red, Color::Black),
     );
-    plot(
+    screen.plot(
         '!',
         GAME_OVER_X+9,
         GAME_OVER_Y,
@@ -530,89 +574,269 @@ pub fn draw_game_over(player_score: usize) {
     const SCORE_X: usize = 30;
     const SCORE_Y: usize = 12;
     // Game Over
-    plot(
+    screen.plot(
         'Y',
         SCORE_X,
         SCORE_Y,
         ColorCode::new(Color::Yellow, Color::Black),
     );
-    plot(
+    screen.plot(
         'o',
         SCORE_X+1,
         SCORE_Y,
         ColorCode::new(Color::Yellow, Color::Black),
     );
-    plot(
+    screen.plot(
         'u',
         SCORE_X+2,
         SCORE_Y,
         ColorCode::new(Color::Yellow, Color::Black),
     );
-    plot(
+    screen.plot(
         'r',
         SCORE_X+3,
         SCORE_Y,
         ColorCode::new(Color::Yellow, Color::Black),
     );
-    plot(
+    screen.plot(
         's',
         SCORE_X+5,
         SCORE_Y,
         ColorCode::new(Color::Yellow, Color::Black),
     );
-    plot(
+    screen.plot(
         'c',
         SCORE_X+6,
         SCORE_Y,
         ColorCode::new(Color::Yellow, Color::Black),
     );
-    plot(
+    screen.plot(
         'o',
         SCORE_X+7,
         SCORE_Y,
         ColorCode::new(Color::Yellow, Color::Black),
     );
-    plot(
+    screen.plot(
         'r',
         SCORE_X+8,
         SCORE_Y,
         ColorCode::new(Color::Yellow, Color::Black),
     );
-    plot(
+    screen.plot(
         'e',
         SCORE_X+9,
         SCORE_Y,
         ColorCode::new(Color::Yellow, Color::Black),
     );
-    plot(
+    screen.plot(
         ':',
         SCORE_X+10,
         SCORE_Y,
         ColorCode::new(Color::Yellow, Color::Black),
     );
 
-    draw_score(SCORE_X+12, SCORE_Y, player_score, Color::Black);
+    draw_score(screen, SCORE_X+12, SCORE_Y, player_score, Color::Black);
+    if let Some(second_score) = second_score {
+        draw_text(screen, "P2 score:", SCORE_X, SCORE_Y+1, ColorCode::new(Color::LightCyan, Color::Black));
+        draw_score(screen, SCORE_X+12, SCORE_Y+1, second_score, Color::Black);
+    }
 
     // Play again
     const OPTIONS_X: usize = 34;
     const OPTIONS_Y: usize = 15;
 
-    plot('Z', OPTIONS_X, OPTIONS_Y, ColorCode::new(Color::White, Color::Green));
-    plot('P', OPTIONS_X+2, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
-    plot('l', OPTIONS_X+3, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
-    plot('a', OPTIONS_X+4, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
-    plot('y', OPTIONS_X+5, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
-    plot('A', OPTIONS_X+7, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
-    plot('g', OPTIONS_X+8, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
-    plot('a', OPTIONS_X+9, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
-    plot('i', OPTIONS_X+10, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
-    plot('n', OPTIONS_X+11, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
+    screen.plot('Z', OPTIONS_X, OPTIONS_Y, ColorCode::new(Color::White, Color::Green));
+    screen.plot('P', OPTIONS_X+2, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
+    screen.plot('l', OPTIONS_X+3, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
+    screen.plot('a', OPTIONS_X+4, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
+    screen.plot('y', OPTIONS_X+5, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
+    screen.plot('A', OPTIONS_X+7, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
+    screen.plot('g', OPTIONS_X+8, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
+    screen.plot('a', OPTIONS_X+9, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
+    screen.plot('i', OPTIONS_X+10, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
+    screen.plot('n', OPTIONS_X+11, OPTIONS_Y, ColorCode::new(Color::White, Color::Black));
 
     // Quit
-    plot('Q', OPTIONS_X, OPTIONS_Y+2, ColorCode::new(Color::White, Color::Red));
-    plot('Q', OPTIONS_X+2, OPTIONS_Y+2, ColorCode::new(Color::White, Color::Black));
-    plot('u', OPTIONS_X+3, OPTIONS_Y+2, ColorCode::new(Color::White, Color::Black));
-    plot('i', OPTIONS_X+4, OPTIONS_Y+2, ColorCode::new(Color::White, Color::Black));
-    plot('t', OPTIONS_X+5, OPTIONS_Y+2, ColorCode::new(Color::White, Color::Black));
+    screen.plot('Q', OPTIONS_X, OPTIONS_Y+2, ColorCode::new(Color::White, Color::Red));
+    screen.plot('Q', OPTIONS_X+2, OPTIONS_Y+2, ColorCode::new(Color::White, Color::Black));
+    screen.plot('u', OPTIONS_X+3, OPTIONS_Y+2, ColorCode::new(Color::White, Color::Black));
+    screen.plot('i', OPTIONS_X+4, OPTIONS_Y+2, ColorCode::new(Color::White, Color::Black));
+    screen.plot('t', OPTIONS_X+5, OPTIONS_Y+2, ColorCode::new(Color::White, Color::Black));
     
-}
\ No newline at end of file
+}
+
+/// Draws the high-score table for the title screen's attract loop.
+pub fn draw_high_scores(screen: &mut impl Screen, table: &HighScores) {
+    const TABLE_X: usize = 30;
+    const TABLE_Y: usize = 4;
+
+    draw_text(screen, "HIGH SCORES", 34, TABLE_Y - 2, ColorCode::new(Color::Yellow, Color::Red));
+    for (i, entry) in table.entries().iter().enumerate() {
+        let y = TABLE_Y + i;
+        let rank = i + 1;
+        let color = ColorCode::new(Color::White, Color::Black);
+        if rank >= 10 {
+            screen.plot((b'0' + (rank / 10) as u8) as char, TABLE_X, y, color);
+        }
+        screen.plot((b'0' + (rank % 10) as u8) as char, TABLE_X + 1, y, color);
+        screen.plot('.', TABLE_X + 2, y, color);
+        for (x, &c) in entry.initials.iter().enumerate() {
+            screen.plot(c as char, TABLE_X + 4 + x, y, ColorCode::new(Color::LightCyan, Color::Black));
+        }
+        draw_score(screen, TABLE_X + 9, y, entry.score, Color::Black);
+    }
+
+    draw_text(screen, "Press", 32, 20, ColorCode::new(Color::White, Color::Black));
+    draw_text(screen, "Z", 38, 20, ColorCode::new(Color::White, Color::LightBlue));
+    draw_text(screen, "to start", 40, 20, ColorCode::new(Color::White, Color::Black));
+}
+
+/// Replaces the game over options with a prompt for the initials `entry` is
+/// collecting.
+pub fn draw_initials_entry(screen: &mut impl Screen, entry: &InitialsEntry) {
+    const ENTRY_Y: usize = 15;
+
+    for x in 20..60 {
+        for y in ENTRY_Y..ENTRY_Y + 3 {
+            screen.plot(' ', x, y, ColorCode::new(Color::Black, Color::Black));
+        }
+    }
+    let player = if entry.player == 0 { "P1" } else { "P2" };
+    draw_text(screen, "New high score,", 25, ENTRY_Y, ColorCode::new(Color::Yellow, Color::Black));
+    draw_text(screen, player, 41, ENTRY_Y, ColorCode::new(Color::Yellow, Color::Black));
+    draw_text(screen, "! Initials?", 43, ENTRY_Y, ColorCode::new(Color::Yellow, Color::Black));
+    for (x, &c) in entry.initials.iter().enumerate() {
+        screen.plot(c as char, 38 + x, ENTRY_Y + 2, ColorCode::new(Color::White, Color::Blue));
+    }
+}
+
+/// Draws the pause menu's box over the playfield, with item `menu.selected`
+/// highlighted.
+pub fn draw_pause_menu(screen: &mut impl Screen, menu: &PauseMenu, muted: bool) {
+    let back = ColorCode::new(Color::White, Color::Blue);
+    for x in MENU_X..MENU_X + MENU_WIDTH {
+        for y in MENU_Y..MENU_Y + MENU_HEIGHT {
+            screen.plot(' ', x, y, back);
+        }
+    }
+    let title = if menu.sound_page { "SOUND" } else { "PAUSED" };
+    let title_x = MENU_X + (MENU_WIDTH - title.len()) / 2;
+    draw_text(screen, title, title_x, MENU_Y + 1, ColorCode::new(Color::Yellow, Color::Blue));
+    for (i, &item) in menu.items().iter().enumerate() {
+        let label = match item {
+            PauseItem::Resume => "Resume",
+            PauseItem::Restart => "Restart",
+            PauseItem::SoundPage => "Sound",
+            PauseItem::Quit => "Quit to title",
+            PauseItem::Sound if muted => "Sound: off",
+            PauseItem::Sound => "Sound: on",
+            PauseItem::Back => "Back",
+        };
+        let color = if i == menu.selected {
+            ColorCode::new(Color::Blue, Color::White)
+        } else {
+            back
+        };
+        draw_text(screen, label, MENU_X + 5, MENU_Y + 3 + i, color);
+    }
+}
+
+/// Draws the settings screen for `config`, with the item `menu` is on
+/// highlighted.
+pub fn draw_settings(screen: &mut impl Screen, config: &GameConfig, menu: &SettingsMenu) {
+    const ITEMS_X: usize = 24;
+    const VALUES_X: usize = 40;
+    const ITEMS_Y: usize = 5;
+
+    draw_text(screen, "SETTINGS", 36, 2, ColorCode::new(Color::Yellow, Color::Red));
+    for i in 0..settings::ITEMS {
+        let y = ITEMS_Y + i;
+        for x in ITEMS_X - 2..ITEMS_X + 36 {
+            screen.plot(' ', x, y, ColorCode::new(Color::Black, Color::Black));
+        }
+        let selected = i == menu.selected;
+        let color = if selected {
+            ColorCode::new(Color::White, Color::LightBlue)
+        } else {
+            ColorCode::new(Color::LightGray, Color::Black)
+        };
+        let value_color = ColorCode::new(Color::LightCyan, Color::Black);
+        match SettingsItem::at(i) {
+            SettingsItem::Difficulty => {
+                draw_text(screen, "Difficulty", ITEMS_X, y, color);
+                draw_text(screen, config.difficulty.name(), VALUES_X + 2, y, value_color);
+                screen.plot(17u8 as char, VALUES_X, y, value_color);
+                screen.plot(16u8 as char, VALUES_X + 3 + config.difficulty.name().len(), y, value_color);
+            }
+            SettingsItem::Lives => {
+                draw_text(screen, "Lives", ITEMS_X, y, color);
+                screen.plot(17u8 as char, VALUES_X, y, value_color);
+                screen.plot((b'0' + config.lives as u8) as char, VALUES_X + 2, y, value_color);
+                screen.plot(16u8 as char, VALUES_X + 4, y, value_color);
+            }
+            SettingsItem::Bind(action) => {
+                draw_text(screen, action.name(), ITEMS_X, y, color);
+                if selected && menu.rebinding {
+                    draw_text(screen, "press a key", VALUES_X, y, ColorCode::new(Color::Yellow, Color::Black));
+                } else {
+                    draw_text(screen, KeyName::new(config.key(action)).as_str(), VALUES_X, y, value_color);
+                }
+            }
+            SettingsItem::Back => draw_text(screen, "Back", ITEMS_X, y, color),
+        }
+    }
+
+    draw_text(
+        screen,
+        "Up/down choose, left/right change, Enter rebind, Esc back",
+        11,
+        20,
+        ColorCode::new(Color::White, Color::Black),
+    );
+}
+
+/// Row of the banner announcing a wave; the rule, if any, goes underneath.
+const BANNER_Y: usize = 2;
+
+/// Announces wave `wave` across the top of the playfield, with the name of
+/// its special rule under it.
+pub fn draw_wave_banner(screen: &mut impl Screen, wave: usize, rule: Rule) {
+    let color = ColorCode::new(Color::Yellow, Color::Black);
+    let digits = wave.max(1).ilog10() as usize + 1;
+    let x = (BUFFER_WIDTH - 5 - digits) / 2;
+    draw_text(screen, "WAVE", x, BANNER_Y, color);
+    for i in 0..digits {
+        let digit = wave / 10_usize.pow((digits - 1 - i) as u32) % 10;
+        screen.plot((b'0' + digit as u8) as char, x + 5 + i, BANNER_Y, color);
+    }
+    if let Some(name) = rule.name() {
+        let color = ColorCode::new(Color::LightRed, Color::Black);
+        draw_text(screen, name, (BUFFER_WIDTH - name.len()) / 2, BANNER_Y + 1, color);
+    }
+}
+
+/// Erases the wave banner.
+pub fn clear_wave_banner(screen: &mut impl Screen) {
+    for y in BANNER_Y..BANNER_Y + 2 {
+        for x in 0..BUFFER_WIDTH {
+            screen.plot(' ', x, y, ColorCode::new(Color::Black, Color::Black));
+        }
+    }
+}
+
+/// Draws a rider materialising at `x`, `y` with `ticks_left` of
+/// `SPAWN_TICKS` to go: a shimmer that fills the sprite from the feet up.
+pub fn draw_materialising(screen: &mut impl Screen, x: usize, y: usize, ticks_left: usize, color: Color) {
+    const SHADES: [u8; 3] = [176, 177, 178];
+    let rows = (SPAWN_TICKS - ticks_left.min(SPAWN_TICKS)) * 3 / SPAWN_TICKS + 1;
+    for dy in 0..3 {
+        for dx in 0..4 {
+            let c = if dy + rows >= 3 {
+                SHADES[(ticks_left + dx + dy) % SHADES.len()] as char
+            } else {
+                ' '
+            };
+            screen.plot_wrapped(c, x + dx, y + dy, ColorCode::new(color, Color::Black));
+        }
+    }
+}
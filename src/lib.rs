@@ -1,61 +1,204 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "kernel", feature(abi_x86_interrupt))]
 
+use body::Body;
+use config::{Action, GameConfig};
+use egg::{Egg, EGG_BONUS, HATCH_TICKS};
 use enemy::{Enemy, EnemyType};
-use level_draw::{draw_game_over, draw_lava, draw_platforms, draw_titlescreen, draw_ui};
+use event::{Event, Events};
+use high_score::{HighScores, InitialsEntry};
+use lance::{knock_apart, resolve, Lance, Outcome, RECOIL_TICKS};
+use level::Level;
+use level_draw::{
+    clear_wave_banner, draw_game_over, draw_high_scores, draw_initials_entry, draw_lava,
+    draw_mode_select, draw_pause_menu, draw_platforms, draw_player_two_ui, draw_settings,
+    draw_materialising, draw_titlescreen, draw_ui, draw_wave_banner,
+};
 use num::Integer;
-use pc_keyboard::{DecodedKey, KeyCode};
+use input::{Input, KeyInput};
+use pc_keyboard::{DecodedKey, KeyCode, KeyState};
+use pause::{PauseItem, PauseMenu};
 use player::Player;
-use pluggable_interrupt_os::vga_buffer::{plot, Color, ColorCode};
+use sound::{Effect, Sequencer};
+use replay::{Playback, Recording};
+use rider::Rider;
+use screen::{Color, Screen, BUFFER_WIDTH};
+use settings::{SettingsItem, SettingsMenu};
+use sweep::{meet, Move};
+use troll::{Hand, Victim, TROLL_WAVE};
+use wave::{Rule, Wave, BANNER_TICKS, SURVIVAL_BONUS};
 
 use core::
     prelude::rust_2024::derive
 ;
 
-mod enemy;
-mod player;
+#[cfg(feature = "kernel")]
+pub mod ata;
+pub mod body;
+pub mod clock;
+pub mod config;
+pub mod console;
+pub mod egg;
+pub mod enemy;
+pub mod event;
+pub mod high_score;
+pub mod input;
+#[cfg(feature = "kernel")]
+pub mod keyboard;
+pub mod lance;
+pub mod level;
+pub mod pause;
+pub mod player;
+pub mod replay;
+pub mod rider;
+pub mod ring;
 mod level_draw;
+pub mod screen;
+pub mod settings;
+pub mod sound;
+#[cfg(feature = "kernel")]
+pub mod speaker;
+pub mod sweep;
+pub mod troll;
+pub mod wave;
 
 // Stretch Goals
-// 1. Two players
-// 2. Bigger logo
-// 3. Birds laughing at you on the game over screen
-
-const GROUND_BOUNDING_BOXES: [(usize, usize, usize, usize); 5] = [
-    (0, 12, 15, 13),
-    (70, 12, 80, 13),
-    (60, 11, 70, 12),
-    (20, 23, 60, 24),
-    (35, 7, 50, 8),
-];
-
-const SPAWN_POINTS: [(usize, usize); 4] = [
-    (7, 9),
-    (74, 9),
-    (39, 20),
-    (42, 4),
-];
+// 1. Bigger logo
+// 2. Birds laughing at you on the game over screen
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-enum State {
+/// Ticks each page of the title screen's attract loop stays up.
+pub const ATTRACT_TICKS: usize = 150;
+
+/// Default number of ticks into a wave before a pterodactyl comes hunting.
+pub const PTERODACTYL_DELAY: usize = 900;
+
+/// Ticks a rider takes to materialise on a spawn point. A player can cut it
+/// short with any of their keys.
+pub const SPAWN_TICKS: usize = 24;
+
+/// Points for unhorsing the other player in versus mode.
+pub const PLAYER_BOUNTY: usize = 1000;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum State {
     TitleScreen,
     Playing,
+    /// Play is frozen under the pause menu.
+    Paused,
     GameOver,
+    Settings,
+}
+
+/// The choices on the title screen.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Mode {
+    OnePlayer,
+    /// Two players against the enemies; they bounce off each other.
+    Coop,
+    /// Two players who can also unhorse each other.
+    Versus,
+}
+
+impl Mode {
+    pub fn players(self) -> usize {
+        match self {
+            Mode::OnePlayer => 1,
+            Mode::Coop | Mode::Versus => 2,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Mode::OnePlayer => Mode::Coop,
+            Mode::Coop => Mode::Versus,
+            Mode::Versus => Mode::OnePlayer,
+        }
+    }
+
+    fn prev(self) -> Self {
+        self.next().next()
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Mode::OnePlayer => 0,
+            Mode::Coop => 1,
+            Mode::Versus => 2,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
-pub struct Joust {
-    player: Player,
+pub struct Joust<S> {
+    screen: S,
+    config: GameConfig,
+    level: Level,
+    players: [Player; 2],
+    mode: Mode,
     state: State,
     enemies: [Enemy; 10],
+    eggs: [Egg; 10],
+    /// The lava troll's hand.
+    hand: Hand,
     spawned_enemies: usize,
     wave: usize,
+    wave_ticks: usize,
+    /// The wave tick from which the next rider may spawn.
+    next_spawn: usize,
+    /// Ticks the banner for the coming wave has left to show.
+    banner_ticks: usize,
+    /// Each player's lives when the wave began, for the survival bonus.
+    wave_lives: [usize; 2],
+    pterodactyl_delay: usize,
     ui_drawn: bool,
+    drawn: Sprites,
+    /// Steps run since the game was created.
+    steps: u32,
+    recording: Recording,
+    playback: Option<Playback>,
+    /// The first step at which a replay drifted from its recording.
+    desync: Option<u32>,
+    events: Events,
+    /// Debug console switches: players can't die, and the game only moves
+    /// `single_steps` steps at a time.
+    god: bool,
+    frozen: bool,
+    single_steps: usize,
+    high_scores: HighScores,
+    /// Set while a player who made the high-score table types their initials.
+    initials: Option<InitialsEntry>,
+    title_ticks: usize,
+    /// Whether the title screen is showing the high scores instead of the logo.
+    title_scores: bool,
+    sound: Sequencer,
+    pause_menu: PauseMenu,
+    settings_menu: SettingsMenu,
+    input: Input,
+}
+
+/// Where the moving sprites were when they were last drawn, so `render` can
+/// erase them however many steps have run since.
+#[derive(Copy, Clone, Eq, PartialEq, Default)]
+struct Sprites {
+    players: [Player; 2],
+    enemies: [Enemy; 10],
+    eggs: [Egg; 10],
+    hand: Hand,
+    /// Whether the wave banner was up.
+    banner: bool,
 }
 
 pub fn safe_add<const LIMIT: usize>(a: usize, b: usize) -> usize {
     (a + b).mod_floor(&LIMIT)
 }
 
+/// How many columns apart `a` and `b` are, going whichever way round the
+/// screen is shorter.
+pub fn wrapped_distance(a: usize, b: usize) -> usize {
+    let d = a.abs_diff(b) % BUFFER_WIDTH;
+    d.min(BUFFER_WIDTH - d)
+}
+
 pub fn add1<const LIMIT: usize>(value: usize) -> usize {
     safe_add::<LIMIT>(value, 1)
 }
@@ -64,182 +207,698 @@ pub fn sub1<const LIMIT: usize>(value: usize) -> usize {
     safe_add::<LIMIT>(value, LIMIT - 1)
 }
 
-impl Default for Joust {
+impl<S: Screen + Default> Default for Joust<S> {
     fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S: Screen> Joust<S> {
+    pub fn new(screen: S) -> Self {
+        let config = GameConfig::default();
         Self {
-            player: Player::default(),
+            screen,
+            config,
+            level: Level::for_wave(1),
+            players: [Player::new(&config), Player::second(&config)],
+            mode: Mode::OnePlayer,
             state: State::TitleScreen,
             enemies: [Enemy::default(); 10],
+            eggs: [Egg::default(); 10],
+            hand: Hand::default(),
             spawned_enemies: 0,
             wave: 1,
+            wave_ticks: 0,
+            next_spawn: 0,
+            banner_ticks: 0,
+            wave_lives: [0; 2],
+            pterodactyl_delay: PTERODACTYL_DELAY,
             ui_drawn: false,
+            drawn: Sprites::default(),
+            steps: 0,
+            recording: Recording::default(),
+            playback: None,
+            desync: None,
+            events: Events::default(),
+            god: false,
+            frozen: false,
+            single_steps: 0,
+            high_scores: HighScores::default(),
+            initials: None,
+            title_ticks: 0,
+            title_scores: false,
+            sound: Sequencer::default(),
+            pause_menu: PauseMenu::default(),
+            settings_menu: SettingsMenu::default(),
+            input: Input::default(),
         }
     }
-}
 
-impl Joust {
+    /// Sets how many ticks a wave may last before a pterodactyl appears.
+    pub fn set_pterodactyl_delay(&mut self, ticks: usize) {
+        self.pterodactyl_delay = ticks;
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Replaces the settings; they take effect from the next game.
+    pub fn set_config(&mut self, config: GameConfig) {
+        self.config = config;
+        self.ui_drawn = false;
+    }
+
+    pub fn screen(&self) -> &S {
+        &self.screen
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The first player.
+    pub fn player(&self) -> &Player {
+        &self.players[0]
+    }
+
+    /// The players taking part in the current game.
+    pub fn players(&self) -> impl Iterator<Item = &Player> {
+        self.players.iter().take(self.mode.players())
+    }
+
+    /// The enemies currently alive on the playfield.
+    pub fn enemies(&self) -> impl Iterator<Item = &Enemy> {
+        self.enemies.iter().filter(|enemy| !enemy.body.dead)
+    }
+
+    /// The eggs currently waiting to be collected or to hatch.
+    pub fn eggs(&self) -> impl Iterator<Item = &Egg> {
+        self.eggs.iter().filter(|egg| !egg.body.dead)
+    }
+
+    pub fn score(&self) -> usize {
+        self.players[0].score
+    }
+
+    pub fn lives(&self) -> usize {
+        self.players[0].lives
+    }
+
+    pub fn wave(&self) -> usize {
+        self.wave
+    }
+
+    /// The level being played.
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    /// Runs one simulation step and draws the result.
     pub fn tick(&mut self) {
+        self.step();
+        self.render();
+    }
+
+    pub fn high_scores(&self) -> &HighScores {
+        &self.high_scores
+    }
+
+    /// Replaces the high-score table, e.g. with one loaded from disk.
+    pub fn set_high_scores(&mut self, table: HighScores) {
+        self.high_scores = table;
+        self.ui_drawn = false;
+    }
+
+    /// The initials being typed in for a new high score, if any.
+    pub fn initials(&self) -> Option<&InitialsEntry> {
+        self.initials.as_ref()
+    }
+
+    /// The oldest event not yet taken, if any.
+    pub fn next_event(&mut self) -> Option<Event> {
+        self.events.pop()
+    }
+
+    /// The frequency the PC speaker should be sounding, if any.
+    pub fn tone(&self) -> Option<u16> {
+        self.sound.tone()
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.sound.is_muted()
+    }
+
+    /// Queues `event` and plays its sound effect, if it has one.
+    fn emit(&mut self, event: Event) {
+        let effect = match event {
+            Event::StateChanged(State::GameOver) => Some(Effect::GameOver),
+            Event::WaveStarted(wave) if wave > 1 => Some(Effect::WaveClear),
+            Event::PlayerDied { .. } | Event::PlayerOut(_) => Some(Effect::Death),
+            Event::EnemyDefeated { .. } | Event::PlayerDefeated { .. } => Some(Effect::JoustWin),
+            _ => None,
+        };
+        if let Some(effect) = effect {
+            self.sound.play(effect);
+        }
+        self.events.push(event);
+    }
+
+    /// Flaps player `p`'s wings.
+    fn flap(&mut self, p: usize) {
+        self.players[p].flap(&self.config.physics);
+        self.sound.play(Effect::Flap);
+    }
+
+    /// Advances the game by one fixed-length step without drawing anything.
+    pub fn step(&mut self) {
+        if self.frozen {
+            if self.single_steps == 0 {
+                return;
+            }
+            self.single_steps -= 1;
+        }
+        if let Some(mut playback) = self.playback {
+            while let Some(input) = playback.next_key(&self.recording, self.steps) {
+                self.handle_input(input);
+            }
+            self.update_all();
+            self.steps += 1;
+            if self.desync.is_none() && !playback.matches(&self.recording, self.steps, self.checksum()) {
+                self.desync = Some(self.steps);
+            }
+            self.playback = (self.steps < self.recording.steps()).then_some(playback);
+        } else {
+            self.update_all();
+            self.steps += 1;
+            let checksum = self.checksum();
+            self.recording.push_step(self.steps, checksum);
+        }
+        self.input.end_step();
+        self.sound.tick();
+    }
+
+    /// Everything recorded since the game was created.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Plays `recording` back, ignoring live keys until it runs out; after
+    /// that the game carries on, and keeps recording, as normal. The game must
+    /// be fresh from `new` and set up the same way as the one recorded.
+    pub fn replay(&mut self, recording: &Recording) {
+        debug_assert_eq!(self.steps, 0, "replays start from a new game");
+        self.recording = *recording;
+        self.playback = (recording.steps() > 0).then(Playback::default);
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    /// The first step at which a replay's checksum differed from the one
+    /// recorded, if any.
+    pub fn desync(&self) -> Option<u32> {
+        self.desync
+    }
+
+    /// A checksum of the game state, leaving out the screen and recording.
+    pub fn checksum(&self) -> u64 {
+        replay::checksum(&(
+            self.steps,
+            self.level,
+            self.players,
+            self.mode,
+            self.state,
+            self.enemies,
+            self.eggs,
+            self.hand,
+            (self.spawned_enemies, self.wave, self.wave_ticks, self.pterodactyl_delay),
+            (self.next_spawn, self.banner_ticks, self.wave_lives),
+            (self.config.difficulty, self.config.lives, self.config.physics),
+        ))
+    }
+
+    /// Brings the screen up to date with the game.
+    pub fn render(&mut self) {
         self.clear();
-        self.update_all();
         self.draw_all();
     }
 
     fn state_transition(&mut self, new_state: State) {
         if new_state == State::Playing {
-            self.player = Player::default();
+            self.level = Level::for_wave(1);
+            self.players = [Player::new(&self.config), Player::second(&self.config)];
+            for (player, (x, y)) in self.players.iter_mut().zip(self.level.player_starts()) {
+                player.body.x = x;
+                player.body.y = y;
+            }
+            for player in self.players.iter_mut() {
+                player.god = self.god;
+            }
+            if self.mode == Mode::OnePlayer {
+                self.players[1].body.dead = true;
+                self.players[1].out = true;
+            }
             self.enemies = Default::default();
+            self.eggs = Default::default();
             self.spawned_enemies = 0;
             self.wave = 1;
+            self.wave_ticks = 0;
+            self.next_spawn = 0;
+            self.banner_ticks = 0;
+            self.wave_lives = self.players.map(|player| player.lives);
         } else {
             self.ui_drawn = false;
         }
+        if new_state == State::Settings {
+            self.settings_menu = SettingsMenu::default();
+        }
+        if new_state == State::GameOver {
+            self.initials = self.next_high_scorer(0).map(InitialsEntry::new);
+        }
+        self.title_ticks = 0;
+        self.title_scores = false;
         self.state = new_state;
-        for x in 0..80 {
-            for y in 0..25 {
-                plot(' ', x, y, ColorCode::new(Color::Black, Color::Black));
-            }
+        self.screen.wipe();
+        self.emit(Event::StateChanged(new_state));
+    }
+
+    /// The first player from `first` on whose score makes the high-score table.
+    fn next_high_scorer(&self, first: usize) -> Option<usize> {
+        (first..self.mode.players()).find(|&p| self.high_scores.qualifies(self.players[p].score))
+    }
+
+    /// Clears the playfield and starts wave `wave`, moving to its level.
+    fn start_wave(&mut self, wave: usize) {
+        self.enemies = Default::default();
+        self.eggs = Default::default();
+        self.hand = Hand::default();
+        self.wave = wave;
+        self.spawned_enemies = 0;
+        self.wave_ticks = 0;
+        self.next_spawn = 0;
+        self.banner_ticks = BANNER_TICKS;
+        self.wave_lives = self.players.map(|player| player.lives);
+        let level = Level::for_wave(wave);
+        if level != self.level {
+            self.level = level;
+            self.screen.wipe();
         }
+        self.emit(Event::WaveStarted(wave));
     }
 
-    fn clear(&self) {
+    fn clear(&mut self) {
         match self.state {
             State::TitleScreen => (),
             State::Playing => {
-                self.player.clear();
-                for enemy in self.enemies {
-                    enemy.clear();
+                if self.drawn.banner && self.banner_ticks == 0 {
+                    clear_wave_banner(&mut self.screen);
+                }
+                for player in self.drawn.players {
+                    player.clear(&mut self.screen);
+                }
+                for enemy in self.drawn.enemies {
+                    enemy.clear(&mut self.screen);
                 }
+                for egg in self.drawn.eggs {
+                    egg.clear(&mut self.screen);
+                }
+                self.drawn.hand.clear(&mut self.screen);
             }
-            State::GameOver => (),
+            State::Paused | State::GameOver | State::Settings => (),
         }
     }
 
     fn update_all(&mut self) {
         match self.state {
-            State::TitleScreen => (),
+            State::TitleScreen => {
+                self.title_ticks += 1;
+                if self.title_ticks.is_multiple_of(ATTRACT_TICKS) {
+                    self.title_scores = !self.title_scores;
+                    self.ui_drawn = false;
+                    self.screen.wipe();
+                }
+            }
             State::Playing => {
-                if self.spawned_enemies < self.wave && self.spawned_enemies < 10 {
-                    if let Some((sx, sy)) = self.get_spawn_point(false) {
-                        self.enemies[self.spawned_enemies] = Enemy::default();
-                        self.enemies[self.spawned_enemies].dead = false;
-                        self.enemies[self.spawned_enemies].x = sx;
-                        self.enemies[self.spawned_enemies].y = sy;
-                        if self.spawned_enemies >= 3 && self.spawned_enemies < 5 {
-                            self.enemies[self.spawned_enemies].etype = EnemyType::Hunter
-                        } else if self.spawned_enemies >= 5 {
-                            self.enemies[self.spawned_enemies].etype = EnemyType::ShadowLord
-                        }
-                        self.spawned_enemies += 1;
-                    }
+                self.materialise();
+                self.recover();
+                self.steer();
+                if self.banner_ticks > 0 {
+                    self.banner_ticks -= 1;
+                } else {
+                    self.spawn_riders();
                 }
-                if self.player.dead {
-                    if let Some((sx, sy)) = self.get_spawn_point(true) {
-                        self.player.x = sx;
-                        self.player.y = sy;
-                        self.player.dead = false;
+                for p in 0..self.mode.players() {
+                    if self.players[p].body.dead && !self.players[p].out {
+                        if let Some((sx, sy)) = self.get_spawn_point() {
+                            self.players[p].body.x = sx;
+                            self.players[p].body.y = sy;
+                            self.players[p].body.dead = false;
+                            self.players[p].spawning = SPAWN_TICKS;
+                        }
                     }
                 }
 
-                if self.spawned_enemies == self.wave || self.spawned_enemies == 10 {
-                    let mut all_dead = true;
-                    for i in 0..self.enemies.len() {
-                        if !self.enemies[i].dead {
-                            all_dead = false;
-                        }
-                    }
-                    if all_dead {
-                        self.wave += 1;
-                        self.spawned_enemies = 0;
-                    }
+                if self.banner_ticks == 0
+                    && self.spawned_enemies == Wave::number(self.wave).riders()
+                    && self.enemies().all(|enemy| enemy.etype == EnemyType::Pterodactyl)
+                    && self.eggs().count() == 0
+                {
+                    self.finish_wave();
+                    self.start_wave(self.wave + 1);
                 }
 
 
 
                 for i in 0..self.enemies.len() {
-                    if !self.enemies[i].dead {
-                        self.enemies[i].think(self.player.x, self.player.y);
+                    if !self.enemies[i].body.dead && self.enemies[i].spawning == 0 {
+                        let (px, py) = self.nearest_player(self.enemies[i].body.x);
+                        self.enemies[i].think(px, py, &self.level, &self.config);
                     }
                 }
-                let mut sx = 0;
-                let mut sy = 0;
+                self.troll();
+                let mut moves = [Move::default(); 2];
                 for i in 1..5 {
-                    if let Some(sv) = self.player.update_quarter_step(i, GROUND_BOUNDING_BOXES) {
-                        (sx, sy) = sv;
-                        for j in 0..self.enemies.len() {
-                            if !self.enemies[j].dead {
-                                if let Some((ex, ey)) = self.enemies[j].update_quarter_step(i, GROUND_BOUNDING_BOXES) {
-                                    if self.do_overlap((sx, sy), (sx + 3, sy + 2), (ex, ey), (ex + 3, ey + 2)) && !self.player.dead {
-                                        if sy < ey {
-                                            if self.enemies[j].die() {
-                                                self.enemies[j].dead = true;
-                                            }
-                                            match self.enemies[j].etype {
-                                                EnemyType::Bounder => self.player.score += 250,
-                                                EnemyType::Hunter => self.player.score += 500,
-                                                EnemyType::ShadowLord => self.player.score += 1000,
-                                            };
-                                        } else if ey < sy {
-                                            if self.player.die() {
-                                                self.state_transition(State::GameOver);
-                                                return;
-                                            }
-                                        } else {
-                                            self.player.dx *= -1;
-                                            self.enemies[j].dx *= -1;
-                                        }
-                                    }
-                                    if i == 4 {
-                                        self.enemies[j].x = ex as usize;
-                                        self.enemies[j].y = ey as usize;
-
-                                        if self.enemies[j].dy < 30 && !self.enemies[j].on_ground {
-                                            self.enemies[j].dy += 5;
+                    for (p, step) in moves.iter_mut().enumerate().take(self.mode.players()) {
+                        let before = self.players[p].body.aabb();
+                        if self.players[p].spawning > 0 {
+                            *step = Move { from: before, by: (0, 0) };
+                            continue;
+                        }
+                        let was_dead = self.players[p].body.dead;
+                        if self.players[p].update_quarter_step(i, self.level.platforms()).is_some() {
+                            *step = Move::between(before, self.players[p].body.aabb());
+                            if !was_dead && self.players[p].body.dead {
+                                let lives = self.players[p].lives;
+                                self.emit(Event::PlayerDied { player: p, lives });
+                            }
+                        } else if self.player_out(p) {
+                            return;
+                        }
+                    }
+                    for j in 0..self.enemies.len() {
+                        if !self.enemies[j].body.dead && self.enemies[j].spawning == 0 {
+                            let before = self.enemies[j].body.aabb();
+                            if self.enemies[j].update_quarter_step(i, self.level.platforms()).is_some() {
+                                let enemy_move = Move::between(before, self.enemies[j].body.aabb());
+                                for (p, &player_move) in moves.iter().enumerate().take(self.mode.players()) {
+                                    if let Some(contact) = meet(player_move, enemy_move) {
+                                        if !self.players[p].body.dead
+                                            && self.players[p].spawning == 0
+                                            && self.players[p].recoil == 0
+                                            && !self.enemies[j].body.dead
+                                            && self.enemies[j].recoil == 0
+                                            && self.joust_enemy(p, player_move.cell_at(contact.time), j, enemy_move.cell_at(contact.time))
+                                        {
+                                            return;
                                         }
                                     }
                                 }
+                                if i == 4 && self.enemies[j].etype != EnemyType::Pterodactyl {
+                                    self.enemies[j].body.fall(&self.config.physics);
+                                }
+                            }
+                        }
+                    }
+                    if self.mode.players() == 2
+                        && self.players.iter().all(|player| !player.body.dead && player.spawning == 0 && player.recoil == 0)
+                    {
+                        if let Some(contact) = meet(moves[0], moves[1]) {
+                            if self.joust_players(moves[0].cell_at(contact.time).1, moves[1].cell_at(contact.time).1) {
+                                return;
                             }
                         }
-                    } else {
-                        self.state_transition(State::GameOver);
-                        break;
                     }
                 }
-                if !self.player.dead {
-                    self.player.x = sx as usize;
-                    self.player.y = sy as usize;
-    
-                    if self.player.dy < 30 && !self.player.on_ground {
-                        self.player.dy += 5;
+                for p in 0..self.mode.players() {
+                    if !self.players[p].body.dead && self.players[p].spawning == 0 {
+                        self.players[p].body.fall(&self.config.physics);
                     }
                 }
-                // for enemy in self.enemies {
-                //     if let Some(mut e)  = enemy {
-                //         e.think(self.player.x, self.player.y);
-                //         e.update(GROUND_BOUNDING_BOXES);
-                //         self.player.score = e.score;
-                //     }
-                // }
-                // if self.player.update(GROUND_BOUNDING_BOXES) {
-                //     self.state_transition(State::GameOver);
-                // };
+                match self.hand.held {
+                    Some(Victim::Player(p)) => self.hand.follow(&self.players[p].body),
+                    Some(Victim::Enemy(j)) => self.hand.follow(&self.enemies[j].body),
+                    None => (),
+                }
+
+                self.update_eggs();
+            }
+            State::Paused | State::GameOver | State::Settings => (),
+        }
+    }
+
+    /// Resolves a collision between player `p` at `player_pos` and the enemy in
+    /// slot `j` at `enemy_pos`. Returns true if it ended the game.
+    fn joust_enemy(&mut self, p: usize, player_pos: (isize, isize), j: usize, enemy_pos: (isize, isize)) -> bool {
+        let ((sx, sy), (ex, ey)) = (player_pos, enemy_pos);
+        if self.enemies[j].etype == EnemyType::Pterodactyl {
+            if self.lance_hits_mouth(p, (sx, sy), j, (ex, ey)) {
+                self.enemies[j].die();
+                self.award(p, EnemyType::Pterodactyl, 1000);
+            } else {
+                return self.kill_player(p);
+            }
+            return false;
+        }
+        let lances = (Lance::at(sy, &self.players[p].body), Lance::at(ey, &self.enemies[j].body));
+        match resolve(lances.0, lances.1) {
+            Outcome::First => {
+                self.enemies[j].die();
+                self.lay_egg(Egg::dropped_by(
+                    self.enemies[j].etype,
+                    ex as usize,
+                    ey as usize,
+                    self.enemies[j].body.dx,
+                    self.enemies[j].body.dy,
+                ));
+                let etype = self.enemies[j].etype;
+                match etype {
+                    EnemyType::Bounder => self.award(p, etype, 250),
+                    EnemyType::Hunter => self.award(p, etype, 500),
+                    EnemyType::ShadowLord | EnemyType::Pterodactyl => self.award(p, etype, 1000),
+                };
+            }
+            Outcome::Second => return self.kill_player(p),
+            Outcome::Tie => {
+                knock_apart(&mut self.players[p].body, &mut self.enemies[j].body);
+                let cap = self.config.difficulty.speed_cap(self.enemies[j].etype);
+                self.enemies[j].body.dx = self.enemies[j].body.dx.clamp(-cap, cap);
+                self.players[p].recoil = RECOIL_TICKS;
+                self.enemies[j].recoil = RECOIL_TICKS;
             }
-            State::GameOver => (),
         }
+        false
+    }
+
+    /// Gives player `p` `points` for unhorsing an enemy of type `etype`.
+    fn award(&mut self, p: usize, etype: EnemyType, points: usize) {
+        self.players[p].score += points;
+        self.emit(Event::EnemyDefeated { player: p, etype, points });
     }
 
-    fn get_spawn_point(&self, is_player: bool) -> Option<(usize, usize)> {
-        'outer: for point in SPAWN_POINTS {
+    /// Resolves a collision between the two players, whose sprites are at rows
+    /// `y1` and `y2`. In co-op they only bounce off each other. Returns true if
+    /// it ended the game.
+    fn joust_players(&mut self, y1: isize, y2: isize) -> bool {
+        let outcome = resolve(Lance::at(y1, &self.players[0].body), Lance::at(y2, &self.players[1].body));
+        if self.mode == Mode::Versus && outcome != Outcome::Tie {
+            let (winner, loser) = if outcome == Outcome::First { (0, 1) } else { (1, 0) };
+            self.players[winner].score += PLAYER_BOUNTY;
+            self.emit(Event::PlayerDefeated { winner, loser, points: PLAYER_BOUNTY });
+            return self.kill_player(loser);
+        }
+        let [first, second] = &mut self.players;
+        knock_apart(&mut first.body, &mut second.body);
+        first.recoil = RECOIL_TICKS;
+        second.recoil = RECOIL_TICKS;
+        false
+    }
+
+    /// Unhorses player `p`. Returns true if that ended the game.
+    fn kill_player(&mut self, p: usize) -> bool {
+        if self.players[p].god {
+            return false;
+        }
+        if self.players[p].die() {
+            return self.player_out(p);
+        }
+        let lives = self.players[p].lives;
+        self.emit(Event::PlayerDied { player: p, lives });
+        false
+    }
+
+    /// Takes player `p`, who has no lives left, out of the game, ending it
+    /// once nobody is left. Returns true if the game ended.
+    fn player_out(&mut self, p: usize) -> bool {
+        self.players[p].body.dead = true;
+        self.players[p].out = true;
+        self.emit(Event::PlayerOut(p));
+        if self.players().all(|player| player.out) {
+            self.state_transition(State::GameOver);
+            return true;
+        }
+        false
+    }
+
+    /// The position of the living player horizontally closest to `x`.
+    fn nearest_player(&self, x: usize) -> (usize, usize) {
+        let mut target = &self.players[0];
+        for player in self.players().skip(1) {
+            if target.body.dead || (!player.body.dead && wrapped_distance(player.body.x, x) < wrapped_distance(target.body.x, x)) {
+                target = player;
+            }
+        }
+        (target.body.x, target.body.y)
+    }
+
+    fn free_enemy_slot(&self) -> Option<usize> {
+        self.enemies.iter().position(|enemy| enemy.body.dead)
+    }
+
+    /// Runs one tick of the wave proper: sets off its rule on the first tick,
+    /// spawns its riders as spawn points and slots free up, and brings on a
+    /// pterodactyl if it drags on.
+    fn spawn_riders(&mut self) {
+        let wave = Wave::number(self.wave);
+        if self.wave_ticks == 0 {
+            match wave.rule {
+                Rule::Eggs => self.lay_eggs(&wave),
+                Rule::Pterodactyl => self.spawn_pterodactyl(),
+                Rule::Normal | Rule::Survival => (),
+            }
+        }
+        if self.spawned_enemies < wave.riders() && self.wave_ticks >= self.next_spawn {
+            if let (Some((x, y)), Some(slot)) = (self.get_spawn_point(), self.free_enemy_slot()) {
+                self.enemies[slot] = Enemy {
+                    body: Rider::body(x, y),
+                    etype: wave.etype(self.spawned_enemies),
+                    spawning: SPAWN_TICKS,
+                    ..Enemy::default()
+                };
+                self.spawned_enemies += 1;
+                self.next_spawn = self.wave_ticks + wave.spawn_delay;
+            }
+        }
+
+        self.wave_ticks += 1;
+        if self.pterodactyl_delay > 0 && self.wave_ticks.is_multiple_of(self.pterodactyl_delay) {
+            self.spawn_pterodactyl();
+        }
+    }
+
+    /// Scatters an egg wave's riders over the platforms as eggs that hatch
+    /// `spawn_delay` apart.
+    fn lay_eggs(&mut self, wave: &Wave) {
+        let platforms = self.level.platforms();
+        for (i, egg) in self.eggs.iter_mut().enumerate().take(wave.riders()) {
+            let (x1, y1, x2, _) = platforms[i % platforms.len()];
+            let column = (i / platforms.len() * 4 + 2) % (x2 - x1).max(1);
+            *egg = Egg {
+                body: Body::new(x1 + column, y1.saturating_sub(1), 1, 1),
+                etype: wave.etype(i),
+                hatch_timer: HATCH_TICKS + i * wave.spawn_delay,
+            };
+        }
+        self.spawned_enemies = wave.riders();
+    }
+
+    /// Pays out the survival bonus if the wave just cleared called for it.
+    fn finish_wave(&mut self) {
+        if Wave::number(self.wave).rule != Rule::Survival {
+            return;
+        }
+        for p in 0..self.mode.players() {
+            let player = &mut self.players[p];
+            if !player.out && player.lives >= self.wave_lives[p] {
+                player.score += SURVIVAL_BONUS;
+                self.emit(Event::WaveBonus { player: p, points: SURVIVAL_BONUS });
+            }
+        }
+    }
+
+    /// Sends a pterodactyl in from the screen edge farthest from the first
+    /// player still alive.
+    fn spawn_pterodactyl(&mut self) {
+        if self.enemies().any(|enemy| enemy.etype == EnemyType::Pterodactyl) {
+            return;
+        }
+        if let Some(slot) = self.free_enemy_slot() {
+            let target = self.players().find(|player| !player.body.dead).unwrap_or(&self.players[0]);
+            let from_right = target.body.x < BUFFER_WIDTH / 2;
+            self.enemies[slot] = Enemy {
+                body: Rider::body(if from_right { BUFFER_WIDTH - 5 } else { 0 }, 2),
+                etype: EnemyType::Pterodactyl,
+                ..Enemy::default()
+            };
+            self.emit(Event::PterodactylArrived);
+        }
+    }
+
+    /// Whether the lance of player `p` at `player_pos` meets the mouth of the
+    /// pterodactyl in slot `j` at `ptero_pos` head on.
+    fn lance_hits_mouth(&self, p: usize, player_pos: (isize, isize), j: usize, ptero_pos: (isize, isize)) -> bool {
+        let (lx, ly) = self.players[p].lance_at(player_pos.0, player_pos.1);
+        let (mx, my) = self.enemies[j].mouth_at(ptero_pos.0, ptero_pos.1);
+        let head_on = (self.players[p].body.dx < 0) != (self.enemies[j].body.dx < 0);
+        head_on && ly == my && wrapped_distance(lx as usize, mx as usize) <= 1
+    }
+
+    fn lay_egg(&mut self, egg: Egg) {
+        if let Some(slot) = self.eggs.iter_mut().find(|egg| egg.body.dead) {
+            *slot = egg;
+        }
+    }
+
+    fn update_eggs(&mut self) {
+        for i in 0..self.eggs.len() {
+            if self.eggs[i].body.dead {
+                continue;
+            }
+            self.eggs[i].update(self.level.platforms(), &self.config.physics);
+
+            let (x, y) = (self.eggs[i].body.x as isize, self.eggs[i].body.y as isize);
+            for p in 0..self.mode.players() {
+                let (px, py) = (self.players[p].body.x as isize, self.players[p].body.y as isize);
+                if !self.eggs[i].body.dead && !self.players[p].body.dead && self.do_overlap((px, py), (px + 3, py + 2), (x, y), (x, y)) {
+                    self.eggs[i].body.dead = true;
+                    self.players[p].score += EGG_BONUS;
+                    self.emit(Event::EggCollected { player: p, points: EGG_BONUS });
+                }
+            }
+
+            if self.eggs[i].ready_to_hatch() {
+                if let Some(slot) = self.free_enemy_slot() {
+                    let egg = self.eggs[i];
+                    self.enemies[slot] = Enemy {
+                        body: Rider::body((egg.body.x + BUFFER_WIDTH - 1) % BUFFER_WIDTH, egg.body.y.saturating_sub(2)),
+                        etype: egg.etype,
+                        ..Enemy::default()
+                    };
+                    self.eggs[i].body.dead = true;
+                    self.emit(Event::EggHatched(egg.etype));
+                }
+            }
+        }
+    }
+
+    fn get_spawn_point(&self) -> Option<(usize, usize)> {
+        'outer: for &point in self.level.spawn_points() {
             for enemy in self.enemies {
-                if !enemy.dead {
-                    if self.do_overlap((enemy.x as isize, enemy.y as isize), (enemy.x as isize + 5, enemy.y as isize + 5), (point.0 as isize, point.1 as isize), (point.0 as isize + 5, point.1 as isize + 3)) {
+                if !enemy.body.dead
+                    && self.do_overlap((enemy.body.x as isize, enemy.body.y as isize), (enemy.body.x as isize + 5, enemy.body.y as isize + 5), (point.0 as isize, point.1 as isize), (point.0 as isize + 5, point.1 as isize + 3)) {
                         continue 'outer
                     }
-                }
             }
-            if !is_player && !self.player.dead {
-                if self.do_overlap((self.player.x as isize, self.player.y as isize), (self.player.x as isize + 5, self.player.y as isize + 5), (point.0 as isize, point.1 as isize), (point.0 as isize + 5, point.1 as isize + 3)) {
-                    continue 'outer
-                }
+            for player in self.players() {
+                if !player.body.dead
+                    && self.do_overlap((player.body.x as isize, player.body.y as isize), (player.body.x as isize + 5, player.body.y as isize + 5), (point.0 as isize, point.1 as isize), (point.0 as isize + 5, point.1 as isize + 3)) {
+                        continue 'outer
+                    }
             }
             return Some(point)
         }
@@ -250,35 +909,92 @@ impl Joust {
         match self.state {
             State::TitleScreen => {
                 if !self.ui_drawn {
-                    draw_titlescreen();
+                    if self.title_scores {
+                        draw_high_scores(&mut self.screen, &self.high_scores);
+                    } else {
+                        draw_titlescreen(&mut self.screen);
+                    }
+                    draw_mode_select(&mut self.screen, self.mode.index());
                     self.ui_drawn = true;
                 }
             }
             State::Playing => {
-                if !self.player.dead {
-                    self.player.draw();
+                for player in self.players {
+                    if player.body.dead {
+                        continue;
+                    }
+                    if player.spawning > 0 {
+                        let color = if player.number == 2 { Color::LightCyan } else { Color::Yellow };
+                        draw_materialising(&mut self.screen, player.body.x, player.body.y, player.spawning, color);
+                    } else {
+                        player.draw(&mut self.screen);
+                    }
                 }
                 for enemy in self.enemies {
-                    if !enemy.dead {
-                        enemy.draw()
+                    if enemy.body.dead {
+                        continue;
+                    }
+                    if enemy.spawning > 0 {
+                        draw_materialising(&mut self.screen, enemy.body.x, enemy.body.y, enemy.spawning, Color::Green);
+                    } else {
+                        enemy.draw(&mut self.screen)
+                    }
+                }
+                for egg in self.eggs {
+                    if !egg.body.dead {
+                        egg.draw(&mut self.screen)
                     }
                 }
+                self.hand.draw(&mut self.screen);
+                self.drawn = Sprites {
+                    players: self.players,
+                    enemies: self.enemies,
+                    eggs: self.eggs,
+                    hand: self.hand,
+                    banner: self.banner_ticks > 0,
+                };
+                if self.banner_ticks > 0 {
+                    draw_wave_banner(&mut self.screen, self.wave, Wave::number(self.wave).rule);
+                }
 
-                draw_platforms();
-                draw_lava();
-                draw_ui(self.player.score, self.player.lives, self.wave);
+                draw_platforms(&mut self.screen, &self.level);
+                draw_lava(&mut self.screen, &self.level);
+                draw_ui(&mut self.screen, self.players[0].score, self.players[0].lives, self.wave);
+                if self.mode.players() == 2 {
+                    draw_player_two_ui(&mut self.screen, self.players[1].score, self.players[1].lives);
+                }
+            }
+            State::Paused => {
+                if !self.ui_drawn {
+                    draw_pause_menu(&mut self.screen, &self.pause_menu, self.sound.is_muted());
+                    self.ui_drawn = true;
+                }
             }
             State::GameOver => {
                 if !self.ui_drawn {
-                    draw_game_over(self.player.score);
+                    let second_score = (self.mode.players() == 2).then_some(self.players[1].score);
+                    draw_game_over(&mut self.screen, self.players[0].score, second_score);
                     self.ui_drawn = true;
                 }
+                if let Some(entry) = self.initials {
+                    draw_initials_entry(&mut self.screen, &entry);
+                }
             },
+            State::Settings => {
+                if !self.ui_drawn {
+                    draw_settings(&mut self.screen, &self.config, &self.settings_menu);
+                    self.ui_drawn = true;
+                }
+            }
         }
     }
 
+    /// Whether the boxes from `l1` to `r1` and `l2` to `r2`, corners
+    /// included, overlap. Columns wrap round the screen's width.
     fn do_overlap(&self, l1: (isize, isize), r1: (isize, isize), l2: (isize, isize), r2: (isize, isize)) -> bool {
-        if l1.0 > r2.0 || l2.0 > r1.0 {
+        // Where the second box starts, counting right from the first.
+        let start = (l2.0 - l1.0).rem_euclid(BUFFER_WIDTH as isize);
+        if start > r1.0 - l1.0 && start + (r2.0 - l2.0) < BUFFER_WIDTH as isize {
             return false
         }
 
@@ -289,47 +1005,301 @@ impl Joust {
         true
     }
 
+    /// Passes on a key going down or up. Play reacts to which keys are held
+    /// rather than to the keyboard's repeats; menus react to fresh presses.
+    pub fn key_event(&mut self, code: KeyCode, state: KeyState) {
+        self.input(KeyInput::Event(code, state));
+    }
+
+    /// Passes on a typed key, used for entering initials.
     pub fn key(&mut self, key: DecodedKey) {
-        match key {
-            DecodedKey::RawKey(code) => self.handle_raw(code),
-            DecodedKey::Unicode(c) => self.handle_unicode(c),
+        self.input(KeyInput::Typed(key));
+    }
+
+    /// Records and handles `input`, leaving out keyboard repeats and typing
+    /// outside initials entry so they don't fill up the recording.
+    fn input(&mut self, input: KeyInput) {
+        if self.playback.is_some() {
+            return;
+        }
+        let matters = match input {
+            KeyInput::Event(code, state) => self.input.changes(code, state),
+            KeyInput::Typed(_) => self.initials.is_some(),
+        };
+        if matters {
+            self.recording.push_key(self.steps, input);
+            self.handle_input(input);
         }
     }
 
-    fn handle_raw(&mut self, key: KeyCode) {
-        if self.state == State::Playing {
-            match key {
-                KeyCode::ArrowLeft => {
-                    self.player.accel_left();
+    fn handle_input(&mut self, input: KeyInput) {
+        match input {
+            KeyInput::Event(code, state) => {
+                if self.input.update(code, state) {
+                    self.handle_press(code);
                 }
-                KeyCode::ArrowRight => {
-                    self.player.accel_right();
-                }
-                _ => {}
             }
+            KeyInput::Typed(DecodedKey::Unicode(c)) if self.initials.is_some() => self.enter_initial(c),
+            KeyInput::Typed(_) => {}
         }
     }
 
-    fn handle_unicode(&mut self, key: char) {
+    /// Reacts to `code` going down. The current screen's own keys come
+    /// before bound actions, so no binding can lock the player out of a menu.
+    fn handle_press(&mut self, code: KeyCode) {
+        if self.state == State::Settings && self.settings_menu.rebinding {
+            self.rebind(code);
+        } else if self.initials.is_none() && !self.navigate(code) {
+            if let Some(action) = self.config.action(code) {
+                self.act(action);
+            }
+        }
+    }
+
+    /// Handles `code` if it is one of the current screen's fixed keys, such as
+    /// the arrows in a menu. Returns false if it isn't.
+    fn navigate(&mut self, code: KeyCode) -> bool {
         match self.state {
-            State::TitleScreen => {
-                if key == 'z' {
-                    self.state_transition(State::Playing);
+            State::TitleScreen => match code {
+                KeyCode::ArrowLeft => {
+                    self.mode = self.mode.prev();
+                    self.ui_drawn = false;
+                }
+                KeyCode::ArrowRight => {
+                    self.mode = self.mode.next();
+                    self.ui_drawn = false;
                 }
+                KeyCode::S => self.state_transition(State::Settings),
+                _ => return false,
             },
-            State::Playing => {
-                if key == 'x' {
-                    self.player.flap();
+            State::Playing if code == KeyCode::Escape => self.pause(),
+            State::Paused => match code {
+                KeyCode::ArrowUp => {
+                    self.pause_menu.up();
+                    self.ui_drawn = false;
+                }
+                KeyCode::ArrowDown => {
+                    self.pause_menu.down();
+                    self.ui_drawn = false;
                 }
+                KeyCode::Escape => self.leave_pause_page(),
+                KeyCode::Return => self.choose(self.pause_menu.item()),
+                _ => return false,
             },
-            State::GameOver => {
-                if key == 'z' {
-                    self.state_transition(State::Playing);
+            State::Settings => match code {
+                KeyCode::ArrowUp => {
+                    self.settings_menu.up();
+                    self.ui_drawn = false;
                 }
-                if key == 'q' {
-                    self.state_transition(State::TitleScreen);
+                KeyCode::ArrowDown => {
+                    self.settings_menu.down();
+                    self.ui_drawn = false;
                 }
+                KeyCode::ArrowLeft => self.change_setting(false),
+                KeyCode::ArrowRight => self.change_setting(true),
+                KeyCode::Escape => self.state_transition(State::TitleScreen),
+                KeyCode::Return => self.choose_setting(),
+                _ => return false,
             },
+            State::GameOver if code == KeyCode::Q => self.state_transition(State::TitleScreen),
+            State::Playing | State::GameOver => return false,
+        }
+        true
+    }
+
+    /// Counts down riders materialising on spawn points.
+    fn materialise(&mut self) {
+        for player in self.players.iter_mut() {
+            player.spawning = player.spawning.saturating_sub(1);
+        }
+        for enemy in self.enemies.iter_mut() {
+            enemy.spawning = enemy.spawning.saturating_sub(1);
+        }
+    }
+
+    /// From `TROLL_WAVE` on, lets the lava troll's hand grab a rider flying
+    /// low over a lava pit, and drags whoever it holds down into the lava
+    /// until they flap hard enough to break free.
+    fn troll(&mut self) {
+        if self.wave < TROLL_WAVE {
+            return;
+        }
+        if let Some(victim) = self.hand.held {
+            let (body, spawning) = match victim {
+                Victim::Player(p) => (&mut self.players[p].body, self.players[p].spawning),
+                Victim::Enemy(j) => (&mut self.enemies[j].body, self.enemies[j].spawning),
+            };
+            if body.dead || spawning > 0 {
+                self.hand.release();
+            } else if self.hand.hold(body) {
+                self.emit(Event::BrokeFree(victim));
+            }
+            return;
+        }
+        if self.hand.rest > 0 {
+            self.hand.rest -= 1;
+            return;
+        }
+        let players = self.players.iter_mut().take(self.mode.players()).enumerate()
+            .filter(|(_, player)| player.spawning == 0 && !player.god)
+            .map(|(p, player)| (Victim::Player(p), &mut player.body));
+        let enemies = self.enemies.iter_mut().enumerate()
+            .filter(|(_, enemy)| enemy.spawning == 0 && enemy.etype != EnemyType::Pterodactyl)
+            .map(|(j, enemy)| (Victim::Enemy(j), &mut enemy.body));
+        let lava = self.level.lava();
+        let grab = players
+            .chain(enemies)
+            .find_map(|(victim, body)| Hand::reaches(body, lava).map(|row| (victim, body, row)));
+        if let Some((victim, body, row)) = grab {
+            self.hand.grab(victim, body, row);
+            self.emit(Event::TrollGrabbed(victim));
+        }
+    }
+
+    /// Counts down the ticks riders knocked apart by a tied joust have to
+    /// wait before they can joust again.
+    fn recover(&mut self) {
+        for player in self.players.iter_mut() {
+            player.recoil = player.recoil.saturating_sub(1);
+        }
+        for enemy in self.enemies.iter_mut() {
+            enemy.recoil = enemy.recoil.saturating_sub(1);
+        }
+    }
+
+    /// Moves each player by the keys held for them this step. A player still
+    /// materialising only listens for a fresh press, which finishes it.
+    fn steer(&mut self) {
+        const CONTROLS: [[Action; 3]; 2] = [
+            [Action::Left1, Action::Right1, Action::Flap1],
+            [Action::Left2, Action::Right2, Action::Flap2],
+        ];
+        for (p, [left, right, flap]) in CONTROLS.into_iter().enumerate().take(self.mode.players()) {
+            if self.players[p].spawning > 0 {
+                if [left, right, flap].iter().any(|&action| self.input.just_pressed(self.config.key(action))) {
+                    self.players[p].spawning = 0;
+                }
+                continue;
+            }
+            if self.input.is_held(self.config.key(left)) {
+                self.players[p].accel_left();
+            }
+            if self.input.is_held(self.config.key(right)) {
+                self.players[p].accel_right();
+            }
+            if self.input.just_pressed(self.config.key(flap)) {
+                self.flap(p);
+            }
+        }
+    }
+
+    /// Carries out `action` if it means anything in the current state.
+    fn act(&mut self, action: Action) {
+        match (self.state, action) {
+            (_, Action::Mute) => {
+                self.sound.toggle_mute();
+                self.ui_drawn = false;
+            }
+            (State::TitleScreen | State::GameOver, Action::Start) => self.state_transition(State::Playing),
+            (State::Playing, Action::Pause) => self.pause(),
+            (State::Paused, Action::Pause) => self.leave_pause_page(),
+            (State::Paused, Action::Start) => self.choose(self.pause_menu.item()),
+            _ => {}
+        }
+    }
+
+    /// Freezes play and opens the pause menu over the playfield.
+    fn pause(&mut self) {
+        self.pause_menu.open(&self.screen);
+        self.state = State::Paused;
+        self.ui_drawn = false;
+        self.emit(Event::StateChanged(State::Paused));
+    }
+
+    /// Takes the pause menu down, putting back the playfield it covered, and
+    /// carries on playing.
+    fn resume(&mut self) {
+        self.pause_menu.close(&mut self.screen);
+        self.state = State::Playing;
+        self.emit(Event::StateChanged(State::Playing));
+    }
+
+    /// Backs out of the pause menu's sound page, or out of the menu.
+    fn leave_pause_page(&mut self) {
+        if self.pause_menu.sound_page {
+            self.pause_menu.flip_page();
+            self.ui_drawn = false;
+        } else {
+            self.resume();
+        }
+    }
+
+    /// Acts on `item` from the pause menu.
+    fn choose(&mut self, item: PauseItem) {
+        match item {
+            PauseItem::Resume => self.resume(),
+            PauseItem::Restart => self.state_transition(State::Playing),
+            PauseItem::Quit => self.state_transition(State::TitleScreen),
+            PauseItem::SoundPage | PauseItem::Back => {
+                self.pause_menu.flip_page();
+                self.ui_drawn = false;
+            }
+            PauseItem::Sound => {
+                self.sound.toggle_mute();
+                self.ui_drawn = false;
+            }
+        }
+    }
+
+    /// Steps the selected setting up or down.
+    fn change_setting(&mut self, up: bool) {
+        match self.settings_menu.item() {
+            SettingsItem::Difficulty if up => self.config.difficulty = self.config.difficulty.next(),
+            SettingsItem::Difficulty => self.config.difficulty = self.config.difficulty.prev(),
+            SettingsItem::Lives => self.config.change_lives(if up { 1 } else { -1 }),
+            SettingsItem::Bind(_) | SettingsItem::Back => return,
+        }
+        self.ui_drawn = false;
+    }
+
+    /// Acts on the selected setting: cycles it, starts rebinding it, or
+    /// leaves the settings screen.
+    fn choose_setting(&mut self) {
+        match self.settings_menu.item() {
+            SettingsItem::Difficulty | SettingsItem::Lives => self.change_setting(true),
+            SettingsItem::Bind(_) => {
+                self.settings_menu.rebinding = true;
+                self.ui_drawn = false;
+            }
+            SettingsItem::Back => self.state_transition(State::TitleScreen),
         }
     }
-}
\ No newline at end of file
+
+    /// Binds `code` to the action being rebound; escape leaves it as it was.
+    fn rebind(&mut self, code: KeyCode) {
+        if let (SettingsItem::Bind(action), false) = (self.settings_menu.item(), code == KeyCode::Escape) {
+            self.config.bind(action, code);
+        }
+        self.settings_menu.rebinding = false;
+        self.ui_drawn = false;
+    }
+
+    /// Passes a typed character to the initials entry, adding the score to
+    /// the table once the initials are confirmed.
+    fn enter_initial(&mut self, key: char) {
+        let Some(mut entry) = self.initials else {
+            return;
+        };
+        if !entry.key(key) {
+            self.initials = Some(entry);
+            return;
+        }
+        let score = self.players[entry.player].score;
+        if let Some(rank) = self.high_scores.insert(entry.initials, score) {
+            self.emit(Event::HighScoreAdded { player: entry.player, rank });
+        }
+        self.initials = self.next_high_scorer(entry.player + 1).map(InitialsEntry::new);
+        self.ui_drawn = false;
+        self.screen.wipe();
+    }
+}
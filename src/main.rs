@@ -2,44 +2,149 @@
 #![no_main]
 
 use crossbeam::atomic::AtomicCell;
-use pc_keyboard::DecodedKey;
-use pluggable_interrupt_joust::Joust;
-use pluggable_interrupt_os::{vga_buffer::clear_screen, HandlerTable};
+use pc_keyboard::{layouts, HandleControl, Keyboard, ScancodeSet1};
+use pluggable_interrupt_joust::{
+    ata::AtaDrive,
+    clock::Clock,
+    console::LineBuffer,
+    event::Event,
+    high_score::{HighScores, SECTOR_SIZE},
+    keyboard,
+    ring::Ring,
+    screen::VgaScreen,
+    speaker::Speaker,
+    Joust,
+};
+use pluggable_interrupt_os::{serial::SERIAL1, serial_println, vga_buffer::clear_screen, HandlerTable};
 
 #[no_mangle]
 pub extern "C" fn _start() -> ! {
     HandlerTable::new()
-        .keyboard(key)
         .timer(tick)
         .startup(startup)
         .cpu_loop(cpu_loop)
         .start()
 }
 
-static LAST_KEY: AtomicCell<Option<DecodedKey>> = AtomicCell::new(None);
-static TICKED: AtomicCell<bool> = AtomicCell::new(false);
+/// What the interrupt handlers pass to `cpu_loop`.
+#[derive(Copy, Clone)]
+enum Interrupt {
+    Scancode(u8),
+    /// Timer interrupts since the last `Ticks` that made it into the queue.
+    Ticks(usize),
+}
+
+/// The timer and keyboard handlers are the producers; they can't interrupt
+/// each other, so they count as one. `cpu_loop` is the consumer.
+static INTERRUPTS: Ring<Interrupt, 128> = Ring::new();
+
+/// Timer interrupts the queue had no room for, sent with the next one.
+static UNSENT_TICKS: AtomicCell<usize> = AtomicCell::new(0);
+
+/// Sector of the scores disk holding the high-score table.
+const HIGH_SCORE_SECTOR: u32 = 0;
 
 fn cpu_loop() -> ! {
-    let mut kernel = Joust::default();
+    keyboard::install(scancode);
+    let mut keyboard = Keyboard::new(ScancodeSet1::new(), layouts::Us104Key, HandleControl::Ignore);
+    let mut kernel = Joust::new(VgaScreen);
+    let mut clock = Clock::default();
+    let mut console = LineBuffer::default();
+    serial_println!("joust debug console; type help");
+    let mut disk = AtaDrive::primary(true);
+    let disk_ok = load_high_scores(&mut kernel, &mut disk);
+    let mut speaker = Speaker::new();
+    let mut tone = None;
+    let mut overflows = 0;
     loop {
-        if let Ok(_) = TICKED.compare_exchange(true, false) {
-            kernel.tick();
+        let mut stepped = false;
+        // SAFETY: `cpu_loop` is the only consumer.
+        while let Some(interrupt) = unsafe { INTERRUPTS.pop() } {
+            match interrupt {
+                Interrupt::Ticks(ticks) => {
+                    for _ in 0..clock.advance(ticks) {
+                        kernel.step();
+                        stepped = true;
+                    }
+                }
+                Interrupt::Scancode(byte) => {
+                    if let Ok(Some(event)) = keyboard.add_byte(byte) {
+                        kernel.key_event(event.code, event.state);
+                        if let Some(key) = keyboard.process_keyevent(event) {
+                            kernel.key(key);
+                        }
+                    }
+                }
+            }
+        }
+        if stepped {
+            kernel.render();
+        }
+        if kernel.tone() != tone {
+            tone = kernel.tone();
+            speaker.set(tone);
+        }
+        if INTERRUPTS.overflows() != overflows {
+            overflows = INTERRUPTS.overflows();
+            serial_println!("interrupt queue full; {} events turned away so far", overflows);
         }
 
-        if let Ok(k) = LAST_KEY.fetch_update(|k| if k.is_some() { Some(None) } else { None }) {
-            if let Some(k) = k {
-                kernel.key(k);
+        while let Some(event) = kernel.next_event() {
+            serial_println!("{}", event);
+            if disk_ok && matches!(event, Event::HighScoreAdded { .. }) {
+                let sector = kernel.high_scores().to_sector();
+                if let Err(e) = disk.write_sector(HIGH_SCORE_SECTOR, &sector) {
+                    serial_println!("saving high scores failed: {:?}", e);
+                }
             }
         }
+        serve_console(&mut kernel, &mut console);
     }
 }
 
-fn key(key: DecodedKey) {
-    LAST_KEY.store(Some(key));
+/// Loads the high-score table from the scores disk, keeping the default
+/// table if the sector is blank or corrupt. Returns whether the disk is there
+/// to save to.
+fn load_high_scores(kernel: &mut Joust<VgaScreen>, disk: &mut AtaDrive) -> bool {
+    if let Err(e) = disk.identify() {
+        serial_println!("no scores disk ({:?}); high scores won't be saved", e);
+        return false;
+    }
+    let mut sector = [0; SECTOR_SIZE];
+    match disk.read_sector(HIGH_SCORE_SECTOR, &mut sector) {
+        Ok(()) => match HighScores::from_sector(&sector) {
+            Some(table) => kernel.set_high_scores(table),
+            None => serial_println!("no saved high scores; using defaults"),
+        },
+        Err(e) => serial_println!("reading high scores failed: {:?}", e),
+    }
+    true
+}
+
+/// Runs any command typed on COM1.
+fn serve_console(kernel: &mut Joust<VgaScreen>, console: &mut LineBuffer) {
+    let byte = SERIAL1.lock().try_receive();
+    if let Ok(byte) = byte {
+        if let Some(line) = console.push(byte) {
+            let _ = kernel.command(line, &mut *SERIAL1.lock());
+        }
+    }
+}
+
+/// Queues a scancode, dropping it if the queue is full.
+fn scancode(byte: u8) {
+    // SAFETY: only interrupt handlers push.
+    let _ = unsafe { INTERRUPTS.push(Interrupt::Scancode(byte)) };
 }
 
+/// Queues a tick, holding it over to the next one if the queue is full.
 fn tick() {
-    TICKED.store(true);
+    let ticks = UNSENT_TICKS.load() + 1;
+    // SAFETY: only interrupt handlers push.
+    match unsafe { INTERRUPTS.push(Interrupt::Ticks(ticks)) } {
+        Ok(()) => UNSENT_TICKS.store(0),
+        Err(_) => UNSENT_TICKS.store(ticks),
+    }
 }
 
 fn startup() {
@@ -0,0 +1,97 @@
+use crate::screen::{Color, ColorCode, Screen};
+
+/// Where the pause menu's box sits on the screen.
+pub const MENU_X: usize = 28;
+pub const MENU_Y: usize = 7;
+pub const MENU_WIDTH: usize = 24;
+pub const MENU_HEIGHT: usize = 10;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PauseItem {
+    Resume,
+    Restart,
+    SoundPage,
+    Quit,
+    /// On the sound page: toggles the sound.
+    Sound,
+    /// On the sound page: goes back to the main page.
+    Back,
+}
+
+const MAIN_PAGE: [PauseItem; 4] = [
+    PauseItem::Resume,
+    PauseItem::Restart,
+    PauseItem::SoundPage,
+    PauseItem::Quit,
+];
+const SOUND_PAGE: [PauseItem; 2] = [PauseItem::Sound, PauseItem::Back];
+
+/// The menu shown over the playfield while the game is paused. It keeps the
+/// cells it covers so the playfield can be put back exactly on resume.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct PauseMenu {
+    pub selected: usize,
+    /// Whether the sound page is showing instead of the main one.
+    pub sound_page: bool,
+    under: [[(char, ColorCode); MENU_WIDTH]; MENU_HEIGHT],
+}
+
+impl Default for PauseMenu {
+    fn default() -> Self {
+        Self {
+            selected: 0,
+            sound_page: false,
+            under: [[(' ', ColorCode::new(Color::Black, Color::Black)); MENU_WIDTH]; MENU_HEIGHT],
+        }
+    }
+}
+
+impl PauseMenu {
+    /// Remembers what the menu is about to cover and shows the main page.
+    pub fn open(&mut self, screen: &impl Screen) {
+        self.selected = 0;
+        self.sound_page = false;
+        for (y, row) in self.under.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                *cell = screen.peek(MENU_X + x, MENU_Y + y);
+            }
+        }
+    }
+
+    /// Puts back what the menu covered.
+    pub fn close(&self, screen: &mut impl Screen) {
+        for (y, row) in self.under.iter().enumerate() {
+            for (x, &(c, color)) in row.iter().enumerate() {
+                screen.plot(c, MENU_X + x, MENU_Y + y, color);
+            }
+        }
+    }
+
+    /// The items on the page showing.
+    pub fn items(&self) -> &'static [PauseItem] {
+        if self.sound_page {
+            &SOUND_PAGE
+        } else {
+            &MAIN_PAGE
+        }
+    }
+
+    pub fn item(&self) -> PauseItem {
+        self.items()[self.selected]
+    }
+
+    pub fn up(&mut self) {
+        let len = self.items().len();
+        self.selected = (self.selected + len - 1) % len;
+    }
+
+    pub fn down(&mut self) {
+        self.selected = (self.selected + 1) % self.items().len();
+    }
+
+    /// Switches between the main and sound pages.
+    pub fn flip_page(&mut self) {
+        self.sound_page = !self.sound_page;
+        self.selected = 0;
+    }
+}
@@ -1,215 +1,113 @@
-use num::Integer;
-use pluggable_interrupt_os::vga_buffer::{plot, Color, ColorCode, BUFFER_HEIGHT, BUFFER_WIDTH};
+use crate::body::{tenths, Body, Physics};
+use crate::config::GameConfig;
+use crate::rider::{self, Rider};
+use crate::screen::{Color, Screen, BUFFER_HEIGHT, BUFFER_WIDTH};
 
-const MOVE_WIDTH: isize = BUFFER_WIDTH as isize - 4;
+/// Fastest a player can fly sideways.
+const SPEED_LIMIT: isize = tenths(40);
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Player {
-    pub x: usize,
-    pub y: usize,
-    pub dx: isize,
-    pub dy: isize,
+    pub body: Body,
     pub score: usize,
     pub lives: usize,
-    pub on_ground: bool,
-    pub dead: bool,
+    /// 1 for the first player, 2 for the second.
+    pub number: usize,
+    /// Ticks left materialising on a spawn point, unable to move or joust.
+    pub spawning: usize,
+    /// Ticks left recoiling from a tied joust, unable to joust again.
+    pub recoil: usize,
+    /// Set once the player has lost their last life.
+    pub out: bool,
+    /// Debug console god mode: nothing can unhorse the player and lava
+    /// bounces them back up.
+    pub god: bool,
 }
 
 impl Default for Player {
     fn default() -> Self {
         Self {
-            x: 39,
-            y: 20,
+            body: Body {
+                on_ground: true,
+                ..Rider::body(39, 20)
+            },
             score: 0,
             lives: 6,
-            dx: 0,
-            dy: 0,
-            on_ground: true,
-            dead: false,
+            number: 1,
+            spawning: 0,
+            recoil: 0,
+            out: false,
+            god: false,
         }
     }
 }
 
 impl Player {
-    pub fn clear(&self) {
-        for x in 0..4 {
-            for y in 0..3 {
-                plot(
-                    ' ',
-                    x + self.x,
-                    y + self.y,
-                    ColorCode::new(Color::Black, Color::Black),
-                )
-            }
+    /// The first player, with the lives `config` starts them on.
+    pub fn new(config: &GameConfig) -> Self {
+        Self {
+            lives: config.lives,
+            ..Self::default()
         }
     }
-    fn is_on_ground(
-        &self,
-        sx: usize,
-        sy: usize,
-        ground_bounding_boxes: [(usize, usize, usize, usize); 5],
-    ) -> bool {
-        for (x1, y1, x2, y2) in ground_bounding_boxes {
-            if sy + 3 >= y1 && sy + 3 < y2 && sx >= x1 && sx + 3 < x2 {
-                return true;
-            }
+
+    /// The second player, starting beside the first.
+    pub fn second(config: &GameConfig) -> Self {
+        let first = Self::new(config);
+        Self {
+            body: Body { x: 50, ..first.body },
+            number: 2,
+            ..first
         }
-        return false;
     }
 
-    pub fn update_quarter_step(&mut self, quarter: isize, ground_bounding_boxes: [(usize, usize, usize, usize); 5]) -> Option<(isize, isize)> {
-        let sx = (self.x as isize + (self.dx * quarter / 40)).mod_floor(&MOVE_WIDTH);
-        let mut sy = self.y as isize + (self.dy * quarter / 40);
+    pub fn clear(&self, screen: &mut impl Screen) {
+        self.body.clear(screen);
+    }
 
-        if !self.dead {
-            if sy >= 23 {
-                if self.die() {
-                    return None
-                }
-            } else if sy < 0 {
-                self.dy = -self.dy;
-                sy = 0
-            }
-            
-            self.on_ground =  self.is_on_ground(sx as usize, sy as usize, ground_bounding_boxes);
-    
-            if self.on_ground && self.dy > 0 {
-                self.dy = 0
+    /// Moves the player through the `quarter`th quarter of this tick,
+    /// returning where they end up, or `None` if they sank into the lava with
+    /// no lives left.
+    pub fn update_quarter_step(&mut self, quarter: isize, ground_bounding_boxes: &[(usize, usize, usize, usize)]) -> Option<(isize, isize)> {
+        if !self.body.dead {
+            self.body.advance(quarter, ground_bounding_boxes);
+            if self.body.sunk() && self.god {
+                self.body.dy = -self.body.dy.abs();
+                self.body.place(self.body.x, BUFFER_HEIGHT - rider::HEIGHT);
+            } else if self.body.sunk() && self.die() {
+                return None;
             }
         }
-        Some((sx, sy))
+        Some((self.body.x as isize, self.body.y as isize))
+    }
 
+    /// The cell holding the tip of the lance when the sprite is at `x`, `y`.
+    pub fn lance_at(&self, x: isize, y: isize) -> (isize, isize) {
+        if self.body.dx < 0 {
+            (x, y)
+        } else {
+            (x + 3, y)
+        }
     }
 
-    pub fn draw(&self) {
-        if self.dx < 0 {
-            plot(
-                '_',
-                self.x,
-                self.y,
-                ColorCode::new(Color::White, Color::Black),
-            );
-            plot(
-                184u8 as char,
-                self.x + 1,
-                self.y,
-                ColorCode::new(Color::Brown, Color::Black),
-            );
-            plot(
-                2u8 as char,
-                self.x + 2,
-                self.y,
-                ColorCode::new(Color::Yellow, Color::Black),
-            );
-            plot(
-                254u8 as char,
-                self.x + 1,
-                self.y + 1,
-                ColorCode::new(Color::Cyan, Color::Black),
-            );
-            plot(
-                254u8 as char,
-                self.x + 2,
-                self.y + 1,
-                ColorCode::new(Color::Cyan, Color::Black),
-            );
-            plot(
-                16u8 as char,
-                self.x + 3,
-                self.y + 1,
-                ColorCode::new(Color::Cyan, Color::Black),
-            );
-            if self.on_ground {
-                plot(
-                    '|' as char,
-                    self.x + 1,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
-                plot(
-                    '\\' as char,
-                    self.x + 2,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
-            } else {
-                plot(
-                    14u8 as char,
-                    self.x + 1,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
-            }
+    pub fn rider(&self) -> Rider {
+        if self.number == 2 {
+            Rider { rider: Color::Pink, bird: Color::LightBlue }
         } else {
-            plot(
-                '_',
-                self.x + 3,
-                self.y,
-                ColorCode::new(Color::White, Color::Black),
-            );
-            plot(
-                213u8 as char,
-                self.x + 2,
-                self.y,
-                ColorCode::new(Color::Brown, Color::Black),
-            );
-            plot(
-                2u8 as char,
-                self.x + 1,
-                self.y,
-                ColorCode::new(Color::Yellow, Color::Black),
-            );
-            plot(
-                254u8 as char,
-                self.x + 2,
-                self.y + 1,
-                ColorCode::new(Color::Cyan, Color::Black),
-            );
-            plot(
-                254u8 as char,
-                self.x + 1,
-                self.y + 1,
-                ColorCode::new(Color::Cyan, Color::Black),
-            );
-            plot(
-                17u8 as char,
-                self.x,
-                self.y + 1,
-                ColorCode::new(Color::Cyan, Color::Black),
-            );
-            if self.on_ground {
-                plot(
-                    '|' as char,
-                    self.x + 2,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
-                plot(
-                    '/' as char,
-                    self.x + 1,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
-            } else {
-                plot(
-                    14u8 as char,
-                    self.x + 2,
-                    self.y + 2,
-                    ColorCode::new(Color::Brown, Color::Black),
-                );
-            }
+            Rider { rider: Color::Yellow, bird: Color::Cyan }
         }
     }
 
+    pub fn draw(&self, screen: &mut impl Screen) {
+        self.rider().draw(screen, &self.body);
+    }
+
     pub fn die(&mut self) -> bool {
         if self.lives > 0 {
             self.lives -= 1;
-            self.x = BUFFER_WIDTH / 2;
-            self.y = BUFFER_HEIGHT / 2;
-            self.dx = 0;
-            self.dy = 0;
+            self.body = Rider::body(BUFFER_WIDTH / 2, BUFFER_HEIGHT / 2);
+            self.body.dead = true;
             self.score += 50;
-            self.dead = true;
         } else {
             return true;
         }
@@ -217,24 +115,14 @@ impl Player {
     }
 
     pub fn accel_left(&mut self) {
-        if self.on_ground {
-            self.dx = self.dx - 4
-        } else {
-            self.dx = self.dx - 3;
-        }
+        self.body.accelerate(if self.body.on_ground { -tenths(4) } else { -tenths(3) }, SPEED_LIMIT);
     }
 
     pub fn accel_right(&mut self) {
-        if self.dx < 40 {
-            if self.on_ground {
-                self.dx = self.dx + 4
-            } else {
-                self.dx = self.dx + 3;
-            }
-        }
+        self.body.accelerate(if self.body.on_ground { tenths(4) } else { tenths(3) }, SPEED_LIMIT);
     }
 
-    pub fn flap(&mut self) {
-        self.dy = -10
+    pub fn flap(&mut self, physics: &Physics) {
+        self.body.dy = -physics.player_flap
     }
 }
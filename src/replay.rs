@@ -0,0 +1,148 @@
+use core::hash::{Hash, Hasher};
+use pc_keyboard::DecodedKey;
+
+use crate::input::KeyInput;
+
+/// Most key events a recording holds.
+pub const MAX_EVENTS: usize = 1024;
+
+/// Steps between checksums of the game state.
+pub const CHECKSUM_INTERVAL: u32 = 64;
+
+/// Most checksums a recording holds, about fifteen minutes of play.
+pub const MAX_CHECKSUMS: usize = 256;
+
+/// The key events and typed keys passed to the game, each stamped with the
+/// step it arrived before, plus a checksum of the game state every
+/// `CHECKSUM_INTERVAL` steps so a replay can tell if it has drifted.
+///
+/// Recording starts when the game is created. Once either buffer is full the
+/// rest of the game is not recorded.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Recording {
+    events: [(u32, KeyInput); MAX_EVENTS],
+    event_count: usize,
+    checksums: [u64; MAX_CHECKSUMS],
+    checksum_count: usize,
+    /// Steps covered by the recording.
+    steps: u32,
+    full: bool,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self {
+            events: [(0, KeyInput::Typed(DecodedKey::Unicode(' '))); MAX_EVENTS],
+            event_count: 0,
+            checksums: [0; MAX_CHECKSUMS],
+            checksum_count: 0,
+            steps: 0,
+            full: false,
+        }
+    }
+}
+
+impl Recording {
+    pub fn events(&self) -> &[(u32, KeyInput)] {
+        &self.events[..self.event_count]
+    }
+
+    /// The checksum taken after every `CHECKSUM_INTERVAL` steps, in order.
+    pub fn checksums(&self) -> &[u64] {
+        &self.checksums[..self.checksum_count]
+    }
+
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// Whether recording stopped early because a buffer filled up.
+    pub fn is_full(&self) -> bool {
+        self.full
+    }
+
+    pub(crate) fn push_key(&mut self, step: u32, key: KeyInput) {
+        if self.full {
+            return;
+        }
+        if self.event_count == MAX_EVENTS {
+            self.full = true;
+            return;
+        }
+        self.events[self.event_count] = (step, key);
+        self.event_count += 1;
+    }
+
+    /// Notes that step `step` has run, leaving the game with `checksum`.
+    pub(crate) fn push_step(&mut self, step: u32, checksum: u64) {
+        if self.full {
+            return;
+        }
+        if step.is_multiple_of(CHECKSUM_INTERVAL) {
+            if self.checksum_count == MAX_CHECKSUMS {
+                self.full = true;
+                return;
+            }
+            self.checksums[self.checksum_count] = checksum;
+            self.checksum_count += 1;
+        }
+        self.steps = step;
+    }
+}
+
+/// How far a replay has got through its recording.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct Playback {
+    next_event: usize,
+}
+
+impl Playback {
+    /// The next recorded key due before step `step` runs, if any.
+    pub(crate) fn next_key(&mut self, recording: &Recording, step: u32) -> Option<KeyInput> {
+        let &(due, key) = recording.events().get(self.next_event)?;
+        if due > step {
+            return None;
+        }
+        self.next_event += 1;
+        Some(key)
+    }
+
+    /// Whether the game, left with `checksum` after step `step`, still agrees
+    /// with the recording.
+    pub(crate) fn matches(&self, recording: &Recording, step: u32, checksum: u64) -> bool {
+        if !step.is_multiple_of(CHECKSUM_INTERVAL) {
+            return true;
+        }
+        let index = (step / CHECKSUM_INTERVAL) as usize - 1;
+        recording.checksums().get(index).is_none_or(|&c| c == checksum)
+    }
+}
+
+/// 64-bit FNV-1a, used to checksum game state.
+pub struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// The FNV-1a checksum of `value`.
+pub fn checksum(value: &impl Hash) -> u64 {
+    let mut hasher = Fnv::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,49 @@
+use crate::body::Body;
+use crate::screen::{Color, ColorCode, Screen};
+
+/// Size of a rider on its bird, in cells.
+pub const WIDTH: usize = 4;
+pub const HEIGHT: usize = 3;
+
+/// How a knight on a flying bird looks. Players and enemies differ only in
+/// their colors; which way they face and whether their legs are down comes
+/// from their `Body`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Rider {
+    pub rider: Color,
+    pub bird: Color,
+}
+
+impl Rider {
+    /// A rider-sized body standing with its top-left corner at `x`, `y`.
+    pub const fn body(x: usize, y: usize) -> Body {
+        Body::new(x, y, WIDTH, HEIGHT)
+    }
+
+    /// Draws the rider at `body`'s position, lance first in the direction it
+    /// is moving, running while on the ground and tucked up in the air.
+    pub fn draw(&self, screen: &mut impl Screen, body: &Body) {
+        let (x, y) = (body.x, body.y);
+        let plain = |color| ColorCode::new(color, Color::Black);
+        // Columns counted from the back of the sprite, so one set of offsets
+        // serves both facings.
+        let facing_left = body.dx < 0;
+        let col = |from_back: usize| if facing_left { x + WIDTH - 1 - from_back } else { x + from_back };
+
+        screen.plot_wrapped('_', col(3), y, plain(Color::White));
+        let saddle = if facing_left { 184u8 } else { 213u8 };
+        screen.plot_wrapped(saddle as char, col(2), y, plain(Color::Brown));
+        screen.plot_wrapped(2u8 as char, col(1), y, plain(self.rider));
+        screen.plot_wrapped(254u8 as char, col(2), y + 1, plain(self.bird));
+        screen.plot_wrapped(254u8 as char, col(1), y + 1, plain(self.bird));
+        let tail = if facing_left { 16u8 } else { 17u8 };
+        screen.plot_wrapped(tail as char, col(0), y + 1, plain(self.bird));
+        if body.on_ground {
+            let stride = if facing_left { '\\' } else { '/' };
+            screen.plot_wrapped('|', col(2), y + 2, plain(Color::Brown));
+            screen.plot_wrapped(stride, col(1), y + 2, plain(Color::Brown));
+        } else {
+            screen.plot_wrapped(14u8 as char, col(2), y + 2, plain(Color::Brown));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A fixed-capacity, lock-free queue for one producer and one consumer, e.g.
+/// interrupt handlers feeding `cpu_loop`. It never allocates or blocks, so it
+/// is safe to push from interrupt context.
+///
+/// A full queue turns pushes away and counts them in `overflows`.
+pub struct Ring<T, const N: usize> {
+    slots: [UnsafeCell<MaybeUninit<T>>; N],
+    /// Items ever taken; only the consumer writes it.
+    head: AtomicUsize,
+    /// Items ever added; only the producer writes it.
+    tail: AtomicUsize,
+    overflows: AtomicUsize,
+}
+
+// The producer and consumer only touch slots the other has handed over
+// through `head` and `tail`.
+unsafe impl<T: Send, const N: usize> Sync for Ring<T, N> {}
+
+impl<T: Copy, const N: usize> Default for Ring<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy, const N: usize> Ring<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            overflows: AtomicUsize::new(0),
+        }
+    }
+
+    /// Adds `value` to the back of the queue, handing it back if the queue is
+    /// full.
+    ///
+    /// # Safety
+    ///
+    /// Only one producer may push at a time. Interrupt handlers that cannot
+    /// interrupt each other count as one producer.
+    pub unsafe fn push(&self, value: T) -> Result<(), T> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        if tail.wrapping_sub(self.head.load(Ordering::Acquire)) == N {
+            self.overflows.fetch_add(1, Ordering::Relaxed);
+            return Err(value);
+        }
+        unsafe { (*self.slots[tail % N].get()).write(value) };
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Takes the value at the front of the queue, if any.
+    ///
+    /// # Safety
+    ///
+    /// Only one consumer may pop at a time.
+    pub unsafe fn pop(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+        if head == self.tail.load(Ordering::Acquire) {
+            return None;
+        }
+        let value = unsafe { (*self.slots[head % N].get()).assume_init() };
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+
+    /// Values waiting to be popped.
+    pub fn len(&self) -> usize {
+        self.tail.load(Ordering::Acquire).wrapping_sub(self.head.load(Ordering::Acquire))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Pushes turned away because the queue was full.
+    pub fn overflows(&self) -> usize {
+        self.overflows.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(feature = "kernel")]
+use pluggable_interrupt_os::vga_buffer;
+#[cfg(feature = "kernel")]
+pub use pluggable_interrupt_os::vga_buffer::{Color, ColorCode, BUFFER_HEIGHT, BUFFER_WIDTH};
+
+#[cfg(not(feature = "kernel"))]
+pub const BUFFER_HEIGHT: usize = 25;
+#[cfg(not(feature = "kernel"))]
+pub const BUFFER_WIDTH: usize = 80;
+
+/// Host stand-in for `pluggable_interrupt_os::vga_buffer::Color`.
+#[cfg(not(feature = "kernel"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Color {
+    Black = 0,
+    Blue = 1,
+    Green = 2,
+    Cyan = 3,
+    Red = 4,
+    Magenta = 5,
+    Brown = 6,
+    LightGray = 7,
+    DarkGray = 8,
+    LightBlue = 9,
+    LightGreen = 10,
+    LightCyan = 11,
+    LightRed = 12,
+    Pink = 13,
+    Yellow = 14,
+    White = 15,
+}
+
+/// Host stand-in for `pluggable_interrupt_os::vga_buffer::ColorCode`.
+#[cfg(not(feature = "kernel"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorCode(u8);
+
+#[cfg(not(feature = "kernel"))]
+impl ColorCode {
+    pub fn new(foreground: Color, background: Color) -> ColorCode {
+        ColorCode((background as u8) << 4 | (foreground as u8))
+    }
+}
+
+/// A character-cell display the game can draw on.
+///
+/// Every draw routine goes through this trait instead of writing to the VGA
+/// buffer directly, so the game can render either to real hardware or to an
+/// in-memory `FrameBuffer`.
+pub trait Screen {
+    /// Draws `c` with the given colors at column `col`, row `row`.
+    fn plot(&mut self, c: char, col: usize, row: usize, color: ColorCode);
+
+    /// Returns the character and colors currently shown at `col`, `row`.
+    fn peek(&self, col: usize, row: usize) -> (char, ColorCode);
+
+    /// Like `plot`, but wraps `col` round the screen's width, so a sprite
+    /// straddling the left and right edges shows on both. Rows below the
+    /// screen, where a sprite has sunk into the lava, are left out.
+    fn plot_wrapped(&mut self, c: char, col: usize, row: usize, color: ColorCode) {
+        let width = self.width();
+        if row < self.height() {
+            self.plot(c, col % width, row, color);
+        }
+    }
+
+    fn width(&self) -> usize {
+        BUFFER_WIDTH
+    }
+
+    fn height(&self) -> usize {
+        BUFFER_HEIGHT
+    }
+
+    /// Fills the whole screen with black.
+    fn wipe(&mut self) {
+        for x in 0..self.width() {
+            for y in 0..self.height() {
+                self.plot(' ', x, y, ColorCode::new(Color::Black, Color::Black));
+            }
+        }
+    }
+}
+
+/// The VGA text buffer used when running as a kernel.
+#[cfg(feature = "kernel")]
+#[derive(Copy, Clone, Eq, PartialEq, Default)]
+pub struct VgaScreen;
+
+#[cfg(feature = "kernel")]
+impl Screen for VgaScreen {
+    fn plot(&mut self, c: char, col: usize, row: usize, color: ColorCode) {
+        vga_buffer::plot(c, col, row, color);
+    }
+
+    fn peek(&self, col: usize, row: usize) -> (char, ColorCode) {
+        vga_buffer::peek(col, row)
+    }
+}
+
+/// An 80x25 screen held in memory, for running the game off the kernel target.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct FrameBuffer {
+    cells: [[(char, ColorCode); BUFFER_WIDTH]; BUFFER_HEIGHT],
+}
+
+impl Default for FrameBuffer {
+    fn default() -> Self {
+        Self {
+            cells: [[(' ', ColorCode::new(Color::Black, Color::Black)); BUFFER_WIDTH];
+                BUFFER_HEIGHT],
+        }
+    }
+}
+
+impl FrameBuffer {
+    /// Iterates over the characters of row `row`, left to right.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = char> + '_ {
+        self.cells[row].iter().map(|(c, _)| *c)
+    }
+}
+
+impl Screen for FrameBuffer {
+    fn plot(&mut self, c: char, col: usize, row: usize, color: ColorCode) {
+        self.cells[row][col] = (c, color);
+    }
+
+    fn peek(&self, col: usize, row: usize) -> (char, ColorCode) {
+        self.cells[row][col]
+    }
+}
@@ -0,0 +1,45 @@
+use crate::config::Action;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SettingsItem {
+    Difficulty,
+    Lives,
+    Bind(Action),
+    Back,
+}
+
+/// Items on the settings screen, top to bottom.
+pub const ITEMS: usize = Action::ALL.len() + 3;
+
+impl SettingsItem {
+    pub fn at(i: usize) -> Self {
+        match i {
+            0 => SettingsItem::Difficulty,
+            1 => SettingsItem::Lives,
+            i if i - 2 < Action::ALL.len() => SettingsItem::Bind(Action::ALL[i - 2]),
+            _ => SettingsItem::Back,
+        }
+    }
+}
+
+/// Where the cursor is on the settings screen.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct SettingsMenu {
+    pub selected: usize,
+    /// Set while waiting for the key to bind to the selected action.
+    pub rebinding: bool,
+}
+
+impl SettingsMenu {
+    pub fn item(&self) -> SettingsItem {
+        SettingsItem::at(self.selected)
+    }
+
+    pub fn up(&mut self) {
+        self.selected = (self.selected + ITEMS - 1) % ITEMS;
+    }
+
+    pub fn down(&mut self) {
+        self.selected = (self.selected + 1) % ITEMS;
+    }
+}
@@ -0,0 +1,108 @@
+/// A tone of `hz` (0 for a rest) held for `steps` simulation steps.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Note {
+    pub hz: u16,
+    pub steps: u8,
+}
+
+const fn note(hz: u16, steps: u8) -> Note {
+    Note { hz, steps }
+}
+
+/// The game's sound effects, from least to most important. A more important
+/// effect cuts off a less important one; the reverse waits its turn.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub enum Effect {
+    Flap,
+    JoustWin,
+    WaveClear,
+    Death,
+    GameOver,
+}
+
+const FLAP: [Note; 1] = [note(880, 1)];
+const JOUST_WIN: [Note; 3] = [note(523, 1), note(659, 1), note(784, 2)];
+const WAVE_CLEAR: [Note; 4] = [note(523, 2), note(659, 2), note(784, 2), note(1047, 4)];
+const DEATH: [Note; 4] = [note(392, 2), note(330, 2), note(262, 2), note(196, 4)];
+const GAME_OVER: [Note; 7] = [
+    note(392, 3),
+    note(0, 1),
+    note(370, 3),
+    note(0, 1),
+    note(349, 3),
+    note(0, 1),
+    note(330, 8),
+];
+
+impl Effect {
+    pub fn notes(self) -> &'static [Note] {
+        match self {
+            Effect::Flap => &FLAP,
+            Effect::JoustWin => &JOUST_WIN,
+            Effect::WaveClear => &WAVE_CLEAR,
+            Effect::Death => &DEATH,
+            Effect::GameOver => &GAME_OVER,
+        }
+    }
+}
+
+/// Plays sound effects one note per step without blocking. `tick` it once per
+/// simulation step and send `tone` to the speaker.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct Sequencer {
+    effect: Option<Effect>,
+    note: usize,
+    /// Steps left on the current note.
+    left: u8,
+    tone: Option<u16>,
+    muted: bool,
+}
+
+impl Sequencer {
+    /// Starts `effect` unless something more important is playing.
+    pub fn play(&mut self, effect: Effect) {
+        if self.muted || self.effect.is_some_and(|playing| playing > effect) {
+            return;
+        }
+        self.effect = Some(effect);
+        self.note = 0;
+        self.left = effect.notes()[0].steps;
+    }
+
+    /// Moves on one step.
+    pub fn tick(&mut self) {
+        let Some(effect) = self.effect else {
+            self.tone = None;
+            return;
+        };
+        let notes = effect.notes();
+        let hz = notes[self.note].hz;
+        self.tone = (hz > 0).then_some(hz);
+        self.left -= 1;
+        if self.left == 0 {
+            self.note += 1;
+            match notes.get(self.note) {
+                Some(next) => self.left = next.steps,
+                None => self.effect = None,
+            }
+        }
+    }
+
+    /// The frequency the speaker should be sounding, if any.
+    pub fn tone(&self) -> Option<u16> {
+        self.tone
+    }
+
+    /// Silences everything until toggled back on.
+    pub fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+        if self.muted {
+            self.effect = None;
+            self.tone = None;
+        }
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+}
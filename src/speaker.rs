@@ -0,0 +1,53 @@
+use x86_64::instructions::port::{Port, PortWriteOnly};
+
+use crate::clock::PIT_HZ;
+
+/// The PC speaker, sounded by square waves from PIT channel 2.
+pub struct Speaker {
+    channel_2: PortWriteOnly<u8>,
+    mode: PortWriteOnly<u8>,
+    gate: Port<u8>,
+}
+
+impl Speaker {
+    pub const fn new() -> Self {
+        Self {
+            channel_2: PortWriteOnly::new(0x42),
+            mode: PortWriteOnly::new(0x43),
+            gate: Port::new(0x61),
+        }
+    }
+
+    /// Sounds `tone` Hz, or goes quiet for `None`.
+    pub fn set(&mut self, tone: Option<u16>) {
+        match tone {
+            Some(hz) if hz > 0 => self.play(hz),
+            _ => self.stop(),
+        }
+    }
+
+    pub fn play(&mut self, hz: u16) {
+        let divisor = (PIT_HZ / hz as u64).min(u16::MAX as u64) as u16;
+        unsafe {
+            // Channel 2, low then high byte, square wave.
+            self.mode.write(0xB6);
+            self.channel_2.write(divisor as u8);
+            self.channel_2.write((divisor >> 8) as u8);
+            let gate = self.gate.read();
+            self.gate.write(gate | 0x03);
+        }
+    }
+
+    pub fn stop(&mut self) {
+        unsafe {
+            let gate = self.gate.read();
+            self.gate.write(gate & !0x03);
+        }
+    }
+}
+
+impl Default for Speaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,126 @@
+use crate::body::SUBCELL;
+use crate::screen::BUFFER_WIDTH;
+
+/// The screen's width in `SUBCELL`ths of a cell.
+const SPAN: isize = BUFFER_WIDTH as isize * SUBCELL;
+
+/// A box in `SUBCELL`ths of a cell. `x1`, `y1` is its top-left corner and
+/// `x2`, `y2` is just past its bottom-right one.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Aabb {
+    pub x1: isize,
+    pub y1: isize,
+    pub x2: isize,
+    pub y2: isize,
+}
+
+impl Aabb {
+    /// The box covering cells `x1..x2` across and `y1..y2` down, the way
+    /// platforms are given.
+    pub const fn cells((x1, y1, x2, y2): (usize, usize, usize, usize)) -> Self {
+        Self {
+            x1: x1 as isize * SUBCELL,
+            y1: y1 as isize * SUBCELL,
+            x2: x2 as isize * SUBCELL,
+            y2: y2 as isize * SUBCELL,
+        }
+    }
+
+    pub fn shifted(self, (dx, dy): (isize, isize)) -> Self {
+        Self { x1: self.x1 + dx, y1: self.y1 + dy, x2: self.x2 + dx, y2: self.y2 + dy }
+    }
+
+    /// Whether the two boxes share any area, including across the wrap.
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        wrapped(*other).any(|other| {
+            self.x1 < other.x2 && other.x1 < self.x2 && self.y1 < other.y2 && other.y1 < self.y2
+        })
+    }
+}
+
+/// `target` and its copies one screen width to either side.
+fn wrapped(target: Aabb) -> impl Iterator<Item = Aabb> {
+    [-SPAN, 0, SPAN].into_iter().map(move |shift| target.shifted((shift, 0)))
+}
+
+/// Where a moving box first touches another.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Contact {
+    /// How far through the move they touch, in `SUBCELL`ths of it.
+    pub time: isize,
+    /// Which side of the other box was hit, pointing out of it: `(0, -1)`
+    /// for its top, `(1, 0)` for its right and so on. `(0, 0)` if the boxes
+    /// overlapped from the start.
+    pub normal: (isize, isize),
+}
+
+/// A box and how far it goes in one quarter step.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Move {
+    pub from: Aabb,
+    pub by: (isize, isize),
+}
+
+impl Move {
+    /// The move from `from` to `to`, going the short way round the screen.
+    pub fn between(from: Aabb, to: Aabb) -> Self {
+        let mut dx = (to.x1 - from.x1).rem_euclid(SPAN);
+        if dx > SPAN / 2 {
+            dx -= SPAN;
+        }
+        Self { from, by: (dx, to.y1 - from.y1) }
+    }
+
+    /// The box `time` `SUBCELL`ths of the way through the move.
+    pub fn at(&self, time: isize) -> Aabb {
+        self.from.shifted((self.by.0 * time / SUBCELL, self.by.1 * time / SUBCELL))
+    }
+
+    /// The on-screen cell of the top-left of the box `time` `SUBCELL`ths of
+    /// the way through the move.
+    pub fn cell_at(&self, time: isize) -> (isize, isize) {
+        let at = self.at(time);
+        (at.x1.rem_euclid(SPAN) / SUBCELL, at.y1.div_euclid(SUBCELL))
+    }
+}
+
+/// The earliest point at which `mover`, moving by `motion`, touches `target`
+/// or one of its copies across the wrap. Boxes that already overlap, or
+/// only slide along each other's edges, don't count.
+pub fn sweep(mover: Aabb, motion: (isize, isize), target: Aabb) -> Option<Contact> {
+    wrapped(target)
+        .filter_map(|target| sweep_one(mover, motion, target))
+        .min_by_key(|contact| contact.time)
+}
+
+/// Where two boxes moving at the same time first touch, seen from the first:
+/// `normal` is the side of `b` that `a` meets. Boxes that overlap from the
+/// start touch at time 0.
+pub fn meet(a: Move, b: Move) -> Option<Contact> {
+    if a.from.overlaps(&b.from) {
+        return Some(Contact { time: 0, normal: (0, 0) });
+    }
+    sweep(a.from, (a.by.0 - b.by.0, a.by.1 - b.by.1), b.from)
+}
+
+fn sweep_one(mover: Aabb, motion: (isize, isize), target: Aabb) -> Option<Contact> {
+    let (x_entry, x_exit) = overlap_times(mover.x1, mover.x2, motion.0, target.x1, target.x2)?;
+    let (y_entry, y_exit) = overlap_times(mover.y1, mover.y2, motion.1, target.y1, target.y2)?;
+    let (entry, exit) = (x_entry.max(y_entry), x_exit.min(y_exit));
+    if entry >= exit || !(0..=SUBCELL).contains(&entry) {
+        return None;
+    }
+    // Corners count as landing on or hitting the top or bottom.
+    let normal = if x_entry > y_entry { (-motion.0.signum(), 0) } else { (0, -motion.1.signum()) };
+    Some(Contact { time: entry, normal })
+}
+
+/// When, in `SUBCELL`ths of the move, the span `a1..a2` moving by `d` starts
+/// and stops overlapping `b1..b2`; `None` if it never does.
+fn overlap_times(a1: isize, a2: isize, d: isize, b1: isize, b2: isize) -> Option<(isize, isize)> {
+    if d == 0 {
+        return (a1 < b2 && b1 < a2).then_some((isize::MIN, isize::MAX));
+    }
+    let (entry, exit) = if d > 0 { (b1 - a2, b2 - a1) } else { (b2 - a1, b1 - a2) };
+    Some((entry * SUBCELL / d, exit * SUBCELL / d))
+}
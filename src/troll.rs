@@ -0,0 +1,127 @@
+use core::fmt;
+
+use crate::body::{tenths, Body};
+use crate::screen::{Color, ColorCode, Screen, BUFFER_WIDTH};
+
+/// First wave in which the lava troll reaches up out of the pits.
+pub const TROLL_WAVE: usize = 4;
+
+/// How many rows above a lava pit a rider's feet can be and still be grabbed.
+pub const REACH: usize = 3;
+
+/// Speed the hand drags whoever it holds down at.
+pub const DRAG: isize = tenths(3);
+
+/// Upward speed a held rider must flap with, all told, to break free: four
+/// flaps for a player and three for an enemy with the default physics.
+pub const GRIP: isize = tenths(40);
+
+/// Ticks the hand stays under the lava after letting go before it can grab
+/// again.
+pub const REST_TICKS: usize = 30;
+
+/// A rider the hand has hold of.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Victim {
+    /// The player numbered from 0.
+    Player(usize),
+    /// The enemy in this slot.
+    Enemy(usize),
+}
+
+impl fmt::Display for Victim {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Victim::Player(p) => write!(f, "P{}", p + 1),
+            Victim::Enemy(j) => write!(f, "enemy {j}"),
+        }
+    }
+}
+
+/// The lava troll's hand. There is only one, and it holds one rider at a
+/// time, drawn as an arm reaching from the lava up to the rider's feet.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Hand {
+    pub held: Option<Victim>,
+    /// Upward speed the rider held has flapped with so far.
+    pub struggle: isize,
+    /// Ticks left before the hand can grab again.
+    pub rest: usize,
+    /// The column the arm is in and the row its fingers are on.
+    pub x: usize,
+    pub y: usize,
+    /// The row of the lava the arm comes out of.
+    pub lava: usize,
+}
+
+impl Hand {
+    /// The row of the lava pit `body` is flying low enough over to be
+    /// grabbed from, if any. Riders on a platform are safe.
+    pub fn reaches(body: &Body, lava: &[(usize, usize, usize)]) -> Option<usize> {
+        if body.on_ground || body.dead {
+            return None;
+        }
+        lava.iter()
+            .find(|&&(x1, y, x2)| {
+                body.y + body.height + REACH > y
+                    && (0..body.width).any(|dx| (x1..x2).contains(&((body.x + dx) % BUFFER_WIDTH)))
+            })
+            .map(|&(_, y, _)| y)
+    }
+
+    /// Takes hold of `victim`, whose body is `body`, from the lava at row
+    /// `lava`.
+    pub fn grab(&mut self, victim: Victim, body: &mut Body, lava: usize) {
+        (self.held, self.struggle, self.lava) = (Some(victim), 0, lava);
+        self.hold(body);
+    }
+
+    /// Drags the held rider's `body` down for another tick, unless the flaps
+    /// it has made while held add up to `GRIP`. Returns true if it broke free.
+    pub fn hold(&mut self, body: &mut Body) -> bool {
+        if body.dy < 0 {
+            self.struggle -= body.dy;
+        }
+        if self.struggle >= GRIP {
+            self.release();
+            return true;
+        }
+        body.dx = 0;
+        body.dy = DRAG;
+        self.follow(body);
+        false
+    }
+
+    /// Moves the hand to the feet of the rider held, at `body`.
+    pub fn follow(&mut self, body: &Body) {
+        self.x = (body.x + body.width / 2) % BUFFER_WIDTH;
+        self.y = body.y + body.height;
+    }
+
+    /// Lets go of whoever is held and sinks back under the lava to rest.
+    pub fn release(&mut self) {
+        self.held = None;
+        self.rest = REST_TICKS;
+    }
+
+    /// Erases the arm.
+    pub fn clear(&self, screen: &mut impl Screen) {
+        if self.held.is_some() {
+            for y in self.y..self.lava {
+                screen.plot_wrapped(' ', self.x, y, ColorCode::new(Color::Black, Color::Black));
+            }
+        }
+    }
+
+    /// Draws the arm from the lava up to the fingers, if it holds anyone.
+    pub fn draw(&self, screen: &mut impl Screen) {
+        if self.held.is_none() {
+            return;
+        }
+        let color = ColorCode::new(Color::LightGray, Color::Black);
+        for y in self.y..self.lava {
+            let c = if y == self.y { 'W' } else { 179u8 as char };
+            screen.plot_wrapped(c, self.x, y, color);
+        }
+    }
+}
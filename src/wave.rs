@@ -0,0 +1,127 @@
+use crate::enemy::EnemyType;
+
+/// Ticks the banner announcing a wave stays up before its riders appear.
+pub const BANNER_TICKS: usize = 36;
+
+/// Points for getting through a survival wave without losing a life.
+pub const SURVIVAL_BONUS: usize = 3000;
+
+/// Most riders a wave can send, however far past the table it is.
+pub const MAX_RIDERS: usize = 20;
+
+/// Most eggs an egg wave lays; the playfield holds no more.
+pub const MAX_EGGS: usize = 10;
+
+/// Shortest gap between spawns in a scaled-up wave.
+const MIN_SPAWN_DELAY: usize = 10;
+
+/// What makes a wave more than its riders.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Rule {
+    Normal,
+    /// The riders start as eggs scattered over the platforms, hatching one by
+    /// one unless they are caught first.
+    Eggs,
+    /// Players who get through the wave without losing a life score
+    /// `SURVIVAL_BONUS`.
+    Survival,
+    /// A pterodactyl hunts from the start of the wave.
+    Pterodactyl,
+}
+
+impl Rule {
+    /// The name shown under the wave number on the banner, if any.
+    pub fn name(self) -> Option<&'static str> {
+        match self {
+            Rule::Normal => None,
+            Rule::Eggs => Some("EGG WAVE"),
+            Rule::Survival => Some("SURVIVAL WAVE"),
+            Rule::Pterodactyl => Some("PTERODACTYL WAVE"),
+        }
+    }
+}
+
+/// Who turns up in a wave and how quickly. Riders come in order: Bounders
+/// first, then Hunters, then ShadowLords.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Wave {
+    pub bounders: usize,
+    pub hunters: usize,
+    pub shadow_lords: usize,
+    /// Ticks between one rider spawning (or egg hatching) and the next.
+    pub spawn_delay: usize,
+    pub rule: Rule,
+}
+
+const fn wave(bounders: usize, hunters: usize, shadow_lords: usize, spawn_delay: usize, rule: Rule) -> Wave {
+    Wave { bounders, hunters, shadow_lords, spawn_delay, rule }
+}
+
+/// The waves in the order they are played. Past the end, the last `LOOP`
+/// waves repeat, harder each time round.
+pub const WAVES: [Wave; 12] = [
+    wave(1, 0, 0, 0, Rule::Normal),
+    wave(2, 0, 0, 30, Rule::Normal),
+    wave(3, 0, 0, 30, Rule::Survival),
+    wave(3, 1, 0, 25, Rule::Normal),
+    wave(2, 2, 0, 20, Rule::Eggs),
+    wave(3, 2, 0, 25, Rule::Normal),
+    wave(3, 2, 1, 25, Rule::Pterodactyl),
+    wave(2, 3, 2, 20, Rule::Survival),
+    wave(2, 3, 3, 20, Rule::Normal),
+    wave(2, 4, 2, 15, Rule::Eggs),
+    wave(2, 4, 3, 20, Rule::Normal),
+    wave(1, 4, 4, 15, Rule::Pterodactyl),
+];
+
+/// Waves at the end of `WAVES` that repeat once it runs out.
+pub const LOOP: usize = 4;
+
+impl Wave {
+    /// Wave `number`, counting from 1. Each time round the loop past the end
+    /// of the table, a Bounder in each wave becomes a Hunter, another Hunter
+    /// and ShadowLord join, and riders come 20% sooner.
+    pub fn number(number: usize) -> Self {
+        let i = number.max(1) - 1;
+        if i < WAVES.len() {
+            return WAVES[i];
+        }
+        let past = i - (WAVES.len() - LOOP);
+        // Past `MAX_RIDERS` laps every wave is all ShadowLords, so stop
+        // counting there and keep `riders()` from overflowing.
+        let (laps, base) = ((past / LOOP).min(MAX_RIDERS), WAVES[WAVES.len() - LOOP + past % LOOP]);
+        let promoted = laps.min(base.bounders);
+        let mut wave = Wave {
+            bounders: base.bounders - promoted,
+            hunters: base.hunters + promoted + laps,
+            shadow_lords: base.shadow_lords + laps,
+            spawn_delay: base.spawn_delay,
+            rule: base.rule,
+        };
+        for _ in 0..laps.min(8) {
+            wave.spawn_delay = (wave.spawn_delay * 4 / 5).max(MIN_SPAWN_DELAY);
+        }
+        let most = if wave.rule == Rule::Eggs { MAX_EGGS } else { MAX_RIDERS };
+        let excess = wave.riders().saturating_sub(most);
+        let hunters_cut = excess.min(wave.hunters);
+        wave.hunters -= hunters_cut;
+        wave.shadow_lords -= excess - hunters_cut;
+        wave
+    }
+
+    /// How many riders the wave sends in all.
+    pub fn riders(&self) -> usize {
+        self.bounders + self.hunters + self.shadow_lords
+    }
+
+    /// The type of the `i`th rider to appear, counting from 0.
+    pub fn etype(&self, i: usize) -> EnemyType {
+        if i < self.bounders {
+            EnemyType::Bounder
+        } else if i < self.bounders + self.hunters {
+            EnemyType::Hunter
+        } else {
+            EnemyType::ShadowLord
+        }
+    }
+}
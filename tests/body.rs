@@ -0,0 +1,102 @@
+use pluggable_interrupt_joust::{
+    body::{tenths, Body, Physics, SUBCELL},
+    level::LEVELS,
+    rider::Rider,
+    screen::BUFFER_WIDTH,
+};
+
+/// Moves `body` through a whole tick.
+fn tick(body: &mut Body, platforms: &[(usize, usize, usize, usize)]) {
+    for quarter in 1..5 {
+        body.advance(quarter, platforms);
+    }
+}
+
+#[test]
+fn speed_is_capped_both_ways() {
+    let mut body = Rider::body(10, 10);
+    for _ in 0..20 {
+        body.accelerate(tenths(3), tenths(40));
+    }
+    assert_eq!(body.dx, tenths(40));
+    for _ in 0..40 {
+        body.accelerate(-tenths(3), tenths(40));
+    }
+    assert_eq!(body.dx, -tenths(40));
+
+    let physics = Physics::default();
+    for _ in 0..20 {
+        body.fall(&physics);
+    }
+    assert_eq!(body.dy, physics.terminal_velocity);
+}
+
+#[test]
+fn bodies_land_on_platforms_and_wrap() {
+    let platforms = LEVELS[0].platforms();
+    let (x1, y1, x2, _) = platforms[0];
+    let mut body = Body { dy: 20, ..Rider::body(x1, y1 - 3) };
+    body.land(platforms);
+    assert!(body.on_ground);
+    assert_eq!(body.dy, 0);
+
+    // Half over the end of the platform it is held up; past it, it falls.
+    assert!(body.stands_on(x2 - 2, y1 - 3, platforms));
+    assert!(!body.stands_on(x2, y1 - 3, platforms));
+
+    let mut body = Body { dx: -SUBCELL, ..Body::new(0, 5, 1, 1) };
+    tick(&mut body, &[]);
+    assert_eq!((body.x, body.y), (BUFFER_WIDTH - 1, 5));
+}
+
+#[test]
+fn slow_bodies_keep_the_fraction_of_a_cell_they_move() {
+    // An eighth of a cell a tick gets somewhere in eight ticks.
+    let mut body = Body { dx: SUBCELL / 8, ..Rider::body(10, 10) };
+    for _ in 0..8 {
+        tick(&mut body, &[]);
+    }
+    assert_eq!((body.x, body.sub_x), (11, 0));
+
+    // Going left round the edge the fraction carries over too.
+    let mut body = Body { dx: -SUBCELL / 4, ..Rider::body(0, 10) };
+    for _ in 0..3 {
+        tick(&mut body, &[]);
+    }
+    assert_eq!((body.x, body.sub_x), (BUFFER_WIDTH - 1, SUBCELL / 4));
+}
+
+#[test]
+fn friction_slows_bodies_where_they_are() {
+    let physics = Physics { ground_friction: SUBCELL / 4, ..Physics::default() };
+    let mut body = Body { dx: SUBCELL, on_ground: true, ..Rider::body(10, 10) };
+    body.fall(&physics);
+    assert_eq!((body.dx, body.dy), (SUBCELL * 3 / 4, 0));
+
+    body.on_ground = false;
+    body.fall(&physics);
+    assert_eq!((body.dx, body.dy), (SUBCELL * 3 / 4, physics.gravity));
+}
+
+#[test]
+fn fast_bodies_stop_at_platforms_instead_of_passing_through() {
+    // A one-row platform, and a body falling three rows a tick from above.
+    let platforms = [(10, 10, 30, 11)];
+    let mut body = Body { dy: 3 * SUBCELL, ..Rider::body(15, 5) };
+    tick(&mut body, &platforms);
+    tick(&mut body, &platforms);
+    assert_eq!((body.y, body.sub_y, body.dy), (7, 0, 0));
+    assert!(body.on_ground);
+
+    // Flying up into it from below it bumps its head.
+    let mut body = Body { dy: -3 * SUBCELL, ..Rider::body(15, 13) };
+    let contact = (1..5).find_map(|quarter| body.advance(quarter, &platforms)).unwrap();
+    assert_eq!(contact.normal, (0, 1));
+    assert_eq!((body.y, body.dy), (11, 0));
+
+    // Flying into its end it bounces back.
+    let mut body = Body { dx: 4 * SUBCELL, ..Rider::body(4, 9) };
+    let contact = (1..5).find_map(|quarter| body.advance(quarter, &platforms)).unwrap();
+    assert_eq!(contact.normal, (-1, 0));
+    assert_eq!((body.x, body.dx), (6, -4 * SUBCELL));
+}
@@ -0,0 +1,76 @@
+use pc_keyboard::KeyCode;
+use pluggable_interrupt_joust::{
+    clock::{Clock, MAX_STEPS},
+    screen::FrameBuffer,
+    Joust,
+};
+
+mod common;
+
+use common::press;
+
+/// Steps run over `seconds` of interrupts at `hz`, one interrupt at a time.
+fn steps_in(mut clock: Clock, hz: u64, seconds: u64) -> usize {
+    (0..hz * seconds).map(|_| clock.advance(1)).sum()
+}
+
+#[test]
+fn default_pit_rate_runs_one_step_per_interrupt() {
+    let mut clock = Clock::default();
+    for _ in 0..100 {
+        assert_eq!(clock.advance(1), 1);
+    }
+}
+
+#[test]
+fn step_rate_does_not_depend_on_interrupt_rate() {
+    // 18.2065 steps a second.
+    for hz in [50, 100, 1000] {
+        assert_eq!(steps_in(Clock::from_hz(hz), hz, 10), 182, "{hz} Hz");
+    }
+    assert_eq!(steps_in(Clock::from_pit_divisor(1193), 1000, 10), 182);
+}
+
+#[test]
+fn slow_interrupts_run_several_steps() {
+    let mut clock = Clock::from_hz(6);
+    let steps: Vec<_> = (0..6).map(|_| clock.advance(1)).collect();
+    assert_eq!(steps, [3, 3, 3, 3, 3, 3]);
+}
+
+#[test]
+fn a_long_stall_is_not_fast_forwarded() {
+    let mut clock = Clock::from_hz(1000);
+    assert_eq!(clock.advance(100_000), MAX_STEPS);
+    assert_eq!(clock.advance(1), 0);
+}
+
+/// Plays a game for `interrupts` timer interrupts, flapping about once a
+/// second.
+fn play(mut clock: Clock, interrupts: usize) -> Joust<FrameBuffer> {
+    let mut game: Joust<FrameBuffer> = Joust::default();
+    game.render();
+    press(&mut game, KeyCode::Z);
+    let mut steps = 0;
+    for _ in 0..interrupts {
+        for _ in 0..clock.advance(1) {
+            if steps % 18 == 0 {
+                press(&mut game, KeyCode::X);
+            }
+            game.step();
+            steps += 1;
+        }
+        game.render();
+    }
+    game
+}
+
+#[test]
+fn games_play_the_same_at_any_interrupt_rate() {
+    // Both are 182 steps: ten seconds of play.
+    let reference = play(Clock::default(), 182);
+    let fast = play(Clock::from_hz(1000), 10_000);
+    assert!(reference.enemies().count() + reference.eggs().count() > 0);
+    assert_eq!(fast.player().body.y, reference.player().body.y);
+    assert!(fast == reference);
+}
@@ -0,0 +1,19 @@
+//! Helpers shared by the test files. Each file only uses some of them.
+#![allow(dead_code)]
+
+use pc_keyboard::{KeyCode, KeyState};
+use pluggable_interrupt_joust::{screen::FrameBuffer, Joust};
+
+/// Presses and releases `code` before the next step.
+pub fn press(game: &mut Joust<FrameBuffer>, code: KeyCode) {
+    game.key_event(code, KeyState::Down);
+    game.key_event(code, KeyState::Up);
+}
+
+/// A one-player game, started from the title screen.
+pub fn new_game() -> Joust<FrameBuffer> {
+    let mut game = Joust::default();
+    game.tick();
+    press(&mut game, KeyCode::Z);
+    game
+}
@@ -0,0 +1,126 @@
+use pc_keyboard::{KeyCode, KeyState};
+use pluggable_interrupt_joust::{
+    config::MAX_LIVES,
+    console::{Command, CommandError, LineBuffer},
+    event::Event,
+    level::Level,
+    screen::FrameBuffer,
+    Joust, State,
+};
+
+mod common;
+
+use common::new_game;
+
+fn run(game: &mut Joust<FrameBuffer>, line: &str) -> String {
+    let mut out = String::new();
+    game.command(line, &mut out).unwrap();
+    out
+}
+
+fn events(game: &mut Joust<FrameBuffer>) -> Vec<Event> {
+    core::iter::from_fn(|| game.next_event()).collect()
+}
+
+#[test]
+fn commands_parse() {
+    assert_eq!(Command::parse("dump"), Ok(Command::Dump));
+    assert_eq!(Command::parse("  wave 7 "), Ok(Command::Wave(7)));
+    assert_eq!(Command::parse("lives"), Err(CommandError::BadNumber));
+    assert_eq!(Command::parse("lives many"), Err(CommandError::BadNumber));
+    assert_eq!(Command::parse("lives 10"), Err(CommandError::TooBig(MAX_LIVES)));
+    assert_eq!(Command::parse("god mode"), Err(CommandError::Unknown));
+    assert_eq!(Command::parse("fly"), Err(CommandError::Unknown));
+}
+
+#[test]
+fn serial_bytes_become_lines() {
+    let mut buffer = LineBuffer::default();
+    let lines: Vec<String> = b"\r\nwavx\x7fe 3\rgod\n"
+        .iter()
+        .filter_map(|&b| buffer.push(b).map(String::from))
+        .collect();
+    assert_eq!(lines, ["wave 3", "god"]);
+}
+
+#[test]
+fn game_events_are_logged() {
+    let mut game = new_game();
+    assert_eq!(events(&mut game), [Event::StateChanged(State::Playing)]);
+    // Run off the end of the floor into the lava.
+    game.key_event(KeyCode::ArrowLeft, KeyState::Down);
+    for _ in 0..200 {
+        game.tick();
+    }
+    let log = events(&mut game);
+    assert!(log.contains(&Event::PlayerDied { player: 0, lives: 5 }));
+    assert_eq!(log[0].to_string(), "P1 died, 5 lives left");
+}
+
+#[test]
+fn dump_lists_the_riders() {
+    let mut game = new_game();
+    game.tick();
+    let dump = run(&mut game, "dump");
+    assert!(dump.starts_with("Playing OnePlayer wave 1 step 2"));
+    assert!(dump.contains("P1 at (39, 20)"));
+    assert!(dump.contains("enemy 0 Bounder at (7, 9)"));
+}
+
+#[test]
+fn wave_and_lives_commands_change_the_game() {
+    let mut game: Joust<FrameBuffer> = Joust::default();
+    assert_eq!(run(&mut game, "wave 4"), "error: only during a game\n");
+
+    let mut game = new_game();
+    run(&mut game, "wave 4");
+    assert_eq!(game.wave(), 4);
+    assert_eq!(game.level(), &Level::for_wave(4));
+    assert!(events(&mut game).contains(&Event::WaveStarted(4)));
+
+    run(&mut game, "lives 2");
+    assert_eq!(game.lives(), 2);
+}
+
+#[test]
+fn lives_are_capped() {
+    let mut game = new_game();
+    assert_eq!(run(&mut game, "lives 50"), format!("error: at most {MAX_LIVES}\n"));
+    game.tick();
+    assert_eq!(game.lives(), 6);
+
+    run(&mut game, &format!("lives {MAX_LIVES}"));
+    game.tick();
+    let bar: String = game.screen().row(24).collect();
+    assert_eq!(bar.matches('\u{1}').count(), MAX_LIVES);
+    assert!(bar.contains("Wave:"));
+}
+
+#[test]
+fn god_mode_keeps_the_player_out_of_the_lava() {
+    let mut game = new_game();
+    assert_eq!(run(&mut game, "god"), "god on\n");
+    for _ in 0..300 {
+        game.tick();
+    }
+    assert_eq!(game.lives(), 6);
+    assert!(!game.player().body.dead);
+    assert_eq!(run(&mut game, "god"), "god off\n");
+}
+
+#[test]
+fn step_freezes_the_game_one_step_at_a_time() {
+    let mut game = new_game();
+    game.tick();
+    run(&mut game, "step");
+    let frozen_at = game.checksum();
+    game.tick();
+    let stepped = game.checksum();
+    assert_ne!(stepped, frozen_at);
+    game.tick();
+    assert_eq!(game.checksum(), stepped);
+
+    run(&mut game, "run");
+    game.tick();
+    assert_ne!(game.checksum(), stepped);
+}
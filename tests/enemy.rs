@@ -0,0 +1,93 @@
+use pluggable_interrupt_joust::{
+    config::GameConfig,
+    enemy::{Enemy, EnemyType, Plan},
+    level::LEVELS,
+    rider::Rider,
+};
+
+fn rider(etype: EnemyType, x: usize, y: usize) -> Enemy {
+    Enemy { etype, body: Rider::body(x, y), ..Enemy::default() }
+}
+
+#[test]
+fn riders_flap_clear_of_the_lava() {
+    let level = LEVELS[0];
+    for etype in [EnemyType::Bounder, EnemyType::Hunter, EnemyType::ShadowLord] {
+        // Falling fast over the left-hand pit, with the player below.
+        let mut enemy = rider(etype, 4, 19);
+        enemy.body.dy = 30;
+        assert!(enemy.in_danger(&level));
+        enemy.think(4, 22, &level, &GameConfig::default());
+        assert!(enemy.body.dy < 0, "{:?} fell into the lava", etype);
+    }
+    // Over the floor it is safe to come down.
+    assert!(!rider(EnemyType::Bounder, 30, 19).in_danger(&level));
+}
+
+#[test]
+fn bounders_turn_at_platform_edges() {
+    let level = LEVELS[0];
+    let (x1, y1, x2, _) = level.platforms()[0];
+    let patrol = |x, heading| {
+        let mut enemy = Enemy { heading, plan_ticks: 10, ..rider(EnemyType::Bounder, x, y1 - 3) };
+        enemy.body.on_ground = true;
+        enemy
+    };
+
+    let mut enemy = patrol(x1 + 1, -1);
+    enemy.think(5, 20, &level, &GameConfig::default());
+    assert_eq!((enemy.plan, enemy.heading), (Plan::Patrol, 1));
+
+    let mut enemy = patrol(x2 - 5, 1);
+    enemy.think(5, 20, &level, &GameConfig::default());
+    assert_eq!(enemy.heading, -1);
+    assert_eq!(enemy.body.dy, 0);
+}
+
+#[test]
+fn hunters_climb_above_the_player_before_diving() {
+    let level = LEVELS[0];
+    let mut enemy = rider(EnemyType::Hunter, 10, 12);
+    enemy.think(40, 12, &level, &GameConfig::default());
+    assert_eq!(enemy.plan, Plan::Climb);
+    assert!(enemy.body.dy < 0);
+
+    let mut enemy = rider(EnemyType::Hunter, 10, 6);
+    enemy.think(40, 12, &level, &GameConfig::default());
+    assert_eq!((enemy.plan, enemy.heading, enemy.body.dy), (Plan::Attack, 1, 0));
+}
+
+#[test]
+fn shadow_lords_feint_unless_above() {
+    let level = LEVELS[0];
+    let mut enemy = rider(EnemyType::ShadowLord, 30, 12);
+    enemy.think(35, 12, &level, &GameConfig::default());
+    enemy.think(35, 12, &level, &GameConfig::default());
+    assert_eq!((enemy.plan, enemy.heading), (Plan::Retreat, -1));
+    assert!(enemy.body.dy < 0);
+
+    // Retreating ends and it comes back around.
+    for _ in 0..20 {
+        enemy.body.y = 12;
+        enemy.think(10, 12, &level, &GameConfig::default());
+    }
+    assert_eq!(enemy.plan, Plan::Attack);
+
+    let mut enemy = rider(EnemyType::ShadowLord, 30, 6);
+    enemy.think(35, 12, &level, &GameConfig::default());
+    assert_eq!((enemy.plan, enemy.heading), (Plan::Attack, 1));
+}
+
+#[test]
+fn pterodactyls_swoop_the_short_way_round() {
+    let level = LEVELS[0];
+    let mut enemy = rider(EnemyType::Pterodactyl, 78, 10);
+    enemy.think(1, 12, &level, &GameConfig::default());
+    assert!(enemy.body.dx > 0);
+    // Three columns to go, so the dive is steep.
+    assert!(enemy.body.dy > enemy.body.dx / 2);
+
+    let mut enemy = rider(EnemyType::Pterodactyl, 1, 10);
+    enemy.think(78, 10, &level, &GameConfig::default());
+    assert!(enemy.body.dx < 0);
+}
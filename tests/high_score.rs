@@ -0,0 +1,120 @@
+use pc_keyboard::{DecodedKey, KeyCode};
+use pluggable_interrupt_joust::{
+    event::Event,
+    high_score::{Entry, HighScores, SECTOR_SIZE, TABLE_SIZE},
+    screen::FrameBuffer,
+    Joust, State, ATTRACT_TICKS,
+};
+
+mod common;
+
+use common::press;
+
+fn low_table() -> HighScores {
+    HighScores::new([Entry { initials: *b"LOW", score: 100 }; TABLE_SIZE])
+}
+
+fn high_table() -> HighScores {
+    HighScores::new([Entry { initials: *b"TOP", score: 99_999 }; TABLE_SIZE])
+}
+
+fn row_text(game: &Joust<FrameBuffer>, row: usize) -> String {
+    game.screen().row(row).collect()
+}
+
+/// Plays a one-player game against `table`, leaving the player idle until the
+/// enemies have taken all their lives.
+fn lose_a_game(table: HighScores) -> Joust<FrameBuffer> {
+    let mut game: Joust<FrameBuffer> = Joust::default();
+    game.set_high_scores(table);
+    game.tick();
+    press(&mut game, KeyCode::Z);
+    for _ in 0..5000 {
+        if game.state() != State::Playing {
+            break;
+        }
+        game.tick();
+    }
+    assert_eq!(game.state(), State::GameOver);
+    game.tick();
+    game
+}
+
+fn type_keys(game: &mut Joust<FrameBuffer>, keys: &str) {
+    for c in keys.chars() {
+        game.key(DecodedKey::Unicode(c));
+        game.tick();
+    }
+}
+
+#[test]
+fn scores_go_in_order() {
+    let mut table = HighScores::default();
+    assert!(!table.qualifies(1000));
+    assert_eq!(table.insert(*b"NEW", 500), None);
+    assert_eq!(table.insert(*b"TOP", 99_999), Some(0));
+    assert_eq!(table.insert(*b"MID", 5500), Some(6));
+    let scores: Vec<_> = table.entries().iter().map(|e| e.score).collect();
+    assert_eq!(scores, [99_999, 10_000, 9000, 8000, 7000, 6000, 5500, 5000, 4000, 3000]);
+}
+
+#[test]
+fn tables_survive_a_trip_through_a_sector() {
+    let mut table = HighScores::default();
+    table.insert(*b"ABC", 12_345);
+    assert_eq!(HighScores::from_sector(&table.to_sector()), Some(table));
+}
+
+#[test]
+fn blank_or_corrupt_sectors_are_rejected() {
+    assert_eq!(HighScores::from_sector(&[0; SECTOR_SIZE]), None);
+    let mut sector = HighScores::default().to_sector();
+    sector[10] ^= 1;
+    assert_eq!(HighScores::from_sector(&sector), None);
+}
+
+#[test]
+fn a_qualifying_score_asks_for_initials() {
+    let mut game = lose_a_game(low_table());
+    assert_eq!(game.initials().map(|entry| entry.player), Some(0));
+    assert!(row_text(&game, 15).contains("New high score, P1! Initials?"));
+
+    type_keys(&mut game, "ab\u{8}c\n");
+    assert!(row_text(&game, 17).contains("AC_"));
+    type_keys(&mut game, "zq\n");
+    assert_eq!(game.initials(), None);
+    assert_eq!(game.state(), State::GameOver);
+
+    let top = game.high_scores().entries()[0];
+    assert_eq!(top, Entry { initials: *b"ACZ", score: game.score() });
+    assert_eq!(game.high_scores().entries()[TABLE_SIZE - 1].initials, *b"LOW");
+    assert!(core::iter::from_fn(|| game.next_event())
+        .any(|event| event == Event::HighScoreAdded { player: 0, rank: 0 }));
+
+    press(&mut game, KeyCode::Z);
+    assert_eq!(game.state(), State::Playing);
+}
+
+#[test]
+fn low_scores_skip_the_initials() {
+    let game = lose_a_game(high_table());
+    assert_eq!(game.initials(), None);
+}
+
+#[test]
+fn title_screen_shows_the_high_scores_in_turn() {
+    let mut game: Joust<FrameBuffer> = Joust::default();
+    game.tick();
+    assert!(!row_text(&game, 2).contains("HIGH SCORES"));
+    for _ in 0..ATTRACT_TICKS {
+        game.tick();
+    }
+    assert!(row_text(&game, 2).contains("HIGH SCORES"));
+    assert!(row_text(&game, 4).contains(" 1. JST  000010000"));
+    assert!(row_text(&game, 20).contains("Press Z to start"));
+    for _ in 0..ATTRACT_TICKS {
+        game.tick();
+    }
+    assert!(!row_text(&game, 2).contains("HIGH SCORES"));
+}
+
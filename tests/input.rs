@@ -0,0 +1,74 @@
+use pc_keyboard::{KeyCode, KeyState};
+use pluggable_interrupt_joust::input::Input;
+
+mod common;
+
+use common::new_game;
+
+#[test]
+fn keys_stay_held_until_released() {
+    let mut input = Input::default();
+    assert!(input.update(KeyCode::X, KeyState::Down));
+    assert!(input.is_held(KeyCode::X) && input.just_pressed(KeyCode::X));
+
+    input.end_step();
+    assert!(input.is_held(KeyCode::X) && !input.just_pressed(KeyCode::X));
+
+    // Typematic repeats are not fresh presses, and change nothing.
+    assert!(!input.changes(KeyCode::X, KeyState::Down));
+    assert!(!input.update(KeyCode::X, KeyState::Down));
+    assert!(!input.just_pressed(KeyCode::X));
+
+    assert!(input.changes(KeyCode::X, KeyState::Up));
+    assert!(!input.update(KeyCode::X, KeyState::Up));
+    assert!(!input.is_held(KeyCode::X));
+    assert!(!input.changes(KeyCode::X, KeyState::Up));
+}
+
+#[test]
+fn a_tap_between_steps_still_counts() {
+    let mut input = Input::default();
+    input.update(KeyCode::ArrowLeft, KeyState::Down);
+    input.update(KeyCode::ArrowLeft, KeyState::Up);
+    assert!(input.is_held(KeyCode::ArrowLeft));
+    assert!(input.just_pressed(KeyCode::ArrowLeft));
+    input.end_step();
+    assert!(!input.is_held(KeyCode::ArrowLeft));
+}
+
+#[test]
+fn holding_a_direction_keeps_accelerating() {
+    let mut game = new_game();
+    game.key_event(KeyCode::ArrowLeft, KeyState::Down);
+    game.tick();
+    let first = game.player().body.dx;
+    for _ in 0..3 {
+        // Repeats while held change nothing.
+        game.key_event(KeyCode::ArrowLeft, KeyState::Down);
+        game.tick();
+    }
+    assert!(first < 0);
+    assert_eq!(game.player().body.dx, first * 4);
+
+    game.key_event(KeyCode::ArrowLeft, KeyState::Up);
+    game.tick();
+    assert_eq!(game.player().body.dx, first * 4);
+}
+
+#[test]
+fn moving_and_flapping_at_once() {
+    let mut game = new_game();
+    let (x, y) = (game.player().body.x, game.player().body.y);
+    game.key_event(KeyCode::ArrowRight, KeyState::Down);
+    game.key_event(KeyCode::X, KeyState::Down);
+    game.tick();
+    game.tick();
+    assert!(game.player().body.y < y);
+
+    // Holding flap doesn't keep flapping, but right keeps pushing.
+    for _ in 0..10 {
+        game.tick();
+    }
+    assert!(game.player().body.dy > 0);
+    assert!(game.player().body.x > x);
+}
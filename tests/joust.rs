@@ -0,0 +1,444 @@
+use pc_keyboard::KeyCode;
+use pluggable_interrupt_joust::{
+    body::{tenths, Body, SUBCELL},
+    egg::{EGG_BONUS, HATCH_TICKS},
+    enemy::EnemyType,
+    high_score::{Entry, HighScores, TABLE_SIZE},
+    level::{Level, LEVELS, WAVES_PER_LEVEL},
+    player::Player,
+    rider::Rider,
+    screen::{Color, ColorCode, FrameBuffer, Screen, BUFFER_HEIGHT, BUFFER_WIDTH},
+    wrapped_distance, Joust, Mode, State, PLAYER_BOUNTY, SPAWN_TICKS,
+};
+
+mod common;
+
+use common::{new_game, press};
+
+fn row_text(game: &Joust<FrameBuffer>, row: usize) -> String {
+    game.screen().row(row).collect()
+}
+
+#[test]
+fn title_screen_waits_for_z() {
+    let mut game: Joust<FrameBuffer> = Joust::default();
+    game.tick();
+    assert_eq!(game.state(), State::TitleScreen);
+    assert!(row_text(&game, 20).contains("Press Z to start"));
+
+    press(&mut game, KeyCode::X);
+    game.tick();
+    assert_eq!(game.state(), State::TitleScreen);
+
+    press(&mut game, KeyCode::Z);
+    assert_eq!(game.state(), State::Playing);
+    assert_eq!(game.score(), 0);
+    assert_eq!(game.lives(), 6);
+    assert_eq!(game.wave(), 1);
+}
+
+#[test]
+fn first_enemy_spawns_at_first_free_spawn_point() {
+    let mut game = new_game();
+    assert_eq!(game.enemies().count(), 0);
+    game.tick();
+    let positions: Vec<_> = game.enemies().map(|e| (e.body.x, e.body.y)).collect();
+    assert_eq!(positions, [(7, 9)]);
+}
+
+#[test]
+fn platforms_are_drawn_from_the_level() {
+    let mut game = new_game();
+    game.tick();
+    let brown = ColorCode::new(Color::Brown, Color::Brown);
+    for &(x1, y1, x2, _) in game.level().platforms() {
+        assert_eq!(game.screen().peek(x1, y1).1, brown);
+        assert_eq!(game.screen().peek(x2 - 1, y1).1, brown);
+    }
+    for &(x1, y, x2) in game.level().lava() {
+        assert!(game.screen().row(y).skip(x1).take(x2 - x1).all(|c| c == 178u8 as char));
+    }
+}
+
+#[test]
+fn levels_change_every_few_waves() {
+    assert_eq!(Level::for_wave(1), LEVELS[0]);
+    assert_eq!(Level::for_wave(WAVES_PER_LEVEL), LEVELS[0]);
+    assert_eq!(Level::for_wave(WAVES_PER_LEVEL + 1), LEVELS[1]);
+    assert_eq!(Level::for_wave(WAVES_PER_LEVEL * LEVELS.len() + 1), LEVELS[0]);
+}
+
+#[test]
+fn every_spawn_point_stands_on_a_platform() {
+    for level in LEVELS {
+        for &(x, y) in level.spawn_points().iter().chain(&level.player_starts()) {
+            assert!(
+                level
+                    .platforms()
+                    .iter()
+                    .any(|&(x1, y1, x2, _)| y + 3 == y1 && x >= x1 && x + 3 < x2),
+                "({x}, {y}) is not on a platform"
+            );
+        }
+    }
+}
+
+#[test]
+fn flapping_lifts_the_player() {
+    let mut game = new_game();
+    game.tick();
+    let start = game.player().body.y;
+    press(&mut game, KeyCode::X);
+    game.tick();
+    assert!(game.player().body.y < start);
+}
+
+/// Flaps up past the first Bounder, drifting out from under the platform
+/// overhead, until the player unhorses it, in god mode so the Bounder can't
+/// get the player first.
+fn unhorse_first_bounder(game: &mut Joust<FrameBuffer>) {
+    game.command("god", &mut String::new()).unwrap();
+    press(game, KeyCode::ArrowRight);
+    for tick in 0..400 {
+        if game.score() > 0 {
+            break;
+        }
+        if tick % 3 == 0 {
+            press(game, KeyCode::X);
+        }
+        game.tick();
+    }
+    assert_eq!(game.score(), 250);
+    assert_eq!(game.lives(), 6);
+}
+
+/// Turns the player back from the egg the Bounder dropped, so they don't
+/// land on it and catch it by accident.
+fn back_off(game: &mut Joust<FrameBuffer>) {
+    for _ in 0..2 {
+        press(game, KeyCode::ArrowLeft);
+        game.tick();
+    }
+}
+
+#[test]
+fn unhorsed_bounder_leaves_an_egg() {
+    let mut game = new_game();
+    unhorse_first_bounder(&mut game);
+    assert_eq!(game.enemies().count(), 0);
+    assert_eq!(game.eggs().count(), 1);
+    assert_eq!(game.wave(), 1);
+}
+
+#[test]
+fn egg_falls_and_hatches_into_a_hunter() {
+    let mut game = new_game();
+    unhorse_first_bounder(&mut game);
+    back_off(&mut game);
+    let mut rest = (0, 0);
+    for _ in 0..HATCH_TICKS + 40 {
+        if let Some(egg) = game.eggs().next() {
+            rest = (egg.body.x, egg.body.y);
+        }
+        game.tick();
+        if game.enemies().count() > 0 {
+            break;
+        }
+    }
+    let hatched: Vec<_> = game.enemies().map(|e| e.etype).collect();
+    assert_eq!(hatched, [EnemyType::Hunter]);
+    assert_eq!(game.eggs().count(), 0);
+    // It came to rest on a platform and the rider climbed out there.
+    assert!(game.level().platforms().iter().any(|&(x1, y1, x2, _)| y1 == rest.1 + 1 && (x1..x2).contains(&rest.0)));
+    assert_eq!(game.enemies().next().unwrap().body.y + 2, rest.1);
+}
+
+#[test]
+fn catching_an_egg_scores_a_bonus() {
+    let mut game = new_game();
+    unhorse_first_bounder(&mut game);
+    back_off(&mut game);
+    for _ in 0..20 {
+        game.tick();
+    }
+    assert!(game.eggs().next().unwrap().body.on_ground);
+    // Platforms are solid, so climb up past the left end of the egg's ledge
+    // and hop onto it if need be.
+    for tick in 0..100 {
+        let Some(egg) = game.eggs().next().map(|egg| egg.body) else {
+            break;
+        };
+        let player = game.player().body;
+        let below = player.y + 2 > egg.y;
+        let target = if below { egg.x.saturating_sub(Rider::body(0, 0).width + 1) } else { egg.x };
+        let (toward, heading) = if target < player.x { (KeyCode::ArrowLeft, -1) } else { (KeyCode::ArrowRight, 1) };
+        if player.dx * heading < tenths(8) {
+            press(&mut game, toward);
+        }
+        if below && tick % 2 == 0 {
+            press(&mut game, KeyCode::X);
+        }
+        game.tick();
+    }
+    assert_eq!(game.eggs().count(), 0);
+    assert_eq!(game.score(), 250 + EGG_BONUS);
+    assert_eq!(game.enemies().count(), 0);
+}
+
+#[test]
+fn falling_into_lava_costs_a_life() {
+    let mut game = new_game();
+    for _ in 0..200 {
+        if game.lives() < 6 {
+            break;
+        }
+        press(&mut game, KeyCode::ArrowLeft);
+        game.tick();
+    }
+    assert_eq!(game.lives(), 5);
+    assert_eq!(game.score(), 50);
+}
+
+#[test]
+fn enemies_materialise_before_moving() {
+    let mut game = new_game();
+    game.tick();
+    let enemy = *game.enemies().next().unwrap();
+    assert_eq!(enemy.spawning, SPAWN_TICKS);
+    assert!((176..=178).contains(&(game.screen().peek(enemy.body.x, enemy.body.y + 2).0 as u32)));
+    for _ in 0..SPAWN_TICKS - 1 {
+        game.tick();
+        assert_eq!(game.enemies().next().map(|e| (e.body.x, e.body.y)), Some((enemy.body.x, enemy.body.y)));
+    }
+    game.tick();
+    assert_eq!(game.enemies().next().unwrap().spawning, 0);
+}
+
+#[test]
+fn a_key_press_cuts_a_respawn_short() {
+    let mut game = new_game();
+    while game.lives() == 6 {
+        press(&mut game, KeyCode::ArrowLeft);
+        game.tick();
+    }
+    while game.player().body.dead {
+        game.tick();
+    }
+    let (x, y) = (game.player().body.x, game.player().body.y);
+    assert!(game.player().spawning > 0);
+    for _ in 0..5 {
+        game.tick();
+    }
+    assert_eq!((game.player().body.x, game.player().body.y), (x, y));
+
+    press(&mut game, KeyCode::X);
+    game.tick();
+    assert_eq!(game.player().spawning, 0);
+    press(&mut game, KeyCode::X);
+    game.tick();
+    game.tick();
+    assert!(game.player().body.y < y);
+}
+
+#[test]
+fn losing_every_life_ends_the_game() {
+    let mut game = new_game();
+    // Out of reach, so the game doesn't stop to ask for initials.
+    game.set_high_scores(HighScores::new([Entry { initials: *b"TOP", score: 99_999 }; TABLE_SIZE]));
+    for _ in 0..5000 {
+        if game.state() != State::Playing {
+            break;
+        }
+        press(&mut game, KeyCode::ArrowLeft);
+        game.tick();
+    }
+    assert_eq!(game.state(), State::GameOver);
+    game.tick();
+    assert!(row_text(&game, 10).contains("GAME OVER!"));
+
+    press(&mut game, KeyCode::Q);
+    assert_eq!(game.state(), State::TitleScreen);
+    press(&mut game, KeyCode::Z);
+    assert_eq!(game.state(), State::Playing);
+    assert_eq!(game.lives(), 6);
+    assert_eq!(game.score(), 0);
+}
+
+#[test]
+fn enemies_stay_on_screen() {
+    let mut game = new_game();
+    for tick in 0..1000 {
+        if tick % 3 == 0 {
+            press(&mut game, KeyCode::X);
+        }
+        game.tick();
+        for enemy in game.enemies() {
+            assert!(enemy.body.x < BUFFER_WIDTH);
+            assert!(enemy.body.y + 3 <= BUFFER_HEIGHT);
+        }
+    }
+}
+
+#[test]
+fn riders_wrap_round_the_screen_edges() {
+    let mut player = Player { body: Body { dx: SUBCELL, ..Rider::body(BUFFER_WIDTH - 1, 4) }, ..Player::default() };
+    let steps: Vec<_> = (1..5).map(|quarter| player.update_quarter_step(quarter, LEVELS[0].platforms())).collect();
+    assert_eq!(steps.last(), Some(&Some((0, 4))));
+
+    // Straddling the edge it is drawn on both sides, and stands on the
+    // platforms either side of it.
+    let mut screen = FrameBuffer::default();
+    player.body.x = BUFFER_WIDTH - 2;
+    player.draw(&mut screen);
+    for x in [BUFFER_WIDTH - 2, BUFFER_WIDTH - 1, 0, 1] {
+        assert!((0..3).any(|y| screen.peek(x, player.body.y + y).0 != ' '), "column {x} is blank");
+    }
+    player.body.y = 9;
+    player.body.dy = tenths(5);
+    player.update_quarter_step(1, LEVELS[0].platforms());
+    assert!(player.body.on_ground);
+
+    assert_eq!(wrapped_distance(2, BUFFER_WIDTH - 3), 5);
+    assert_eq!(wrapped_distance(10, 30), 20);
+}
+
+fn pterodactyls(game: &Joust<FrameBuffer>) -> usize {
+    game.enemies()
+        .filter(|enemy| enemy.etype == EnemyType::Pterodactyl)
+        .count()
+}
+
+#[test]
+fn pterodactyl_appears_when_a_wave_drags_on() {
+    let mut game = new_game();
+    game.set_pterodactyl_delay(30);
+    for _ in 0..29 {
+        game.tick();
+    }
+    assert_eq!(pterodactyls(&game), 0);
+    game.tick();
+    assert_eq!(pterodactyls(&game), 1);
+    for _ in 0..200 {
+        game.tick();
+        assert!(pterodactyls(&game) <= 1);
+    }
+}
+
+#[test]
+fn pterodactyl_hunts_down_an_idle_player() {
+    let mut game = new_game();
+    game.set_pterodactyl_delay(1);
+    for _ in 0..20 {
+        if game.lives() < 6 {
+            break;
+        }
+        game.tick();
+    }
+    assert_eq!(game.lives(), 5);
+    assert_eq!(pterodactyls(&game), 1);
+}
+
+fn new_two_player_game(mode: Mode) -> Joust<FrameBuffer> {
+    let mut game: Joust<FrameBuffer> = Joust::default();
+    game.tick();
+    while game.mode() != mode {
+        press(&mut game, KeyCode::ArrowRight);
+    }
+    press(&mut game, KeyCode::Z);
+    game
+}
+
+/// Has player two hop up and drift left onto player one, returning once they
+/// have met.
+fn drop_player_two_on_player_one(game: &mut Joust<FrameBuffer>) {
+    for tick in 0..40 {
+        if tick < 3 {
+            press(game, KeyCode::W);
+        }
+        if tick < 5 {
+            press(game, KeyCode::A);
+        }
+        game.tick();
+        let p2 = game.players().nth(1).unwrap();
+        if p2.body.dx > 0 || p2.score > 0 || game.lives() < 6 {
+            return;
+        }
+    }
+    panic!("player two never reached player one");
+}
+
+#[test]
+fn pterodactyl_comes_from_away_from_the_living_player() {
+    let mut game = new_two_player_game(Mode::Coop);
+    game.command("lives 0", &mut String::new()).unwrap();
+    for _ in 0..200 {
+        if game.player().out {
+            break;
+        }
+        press(&mut game, KeyCode::ArrowLeft);
+        game.tick();
+    }
+    assert!(game.player().out);
+    assert!(game.player().body.x < BUFFER_WIDTH / 2);
+    assert!(game.players().nth(1).unwrap().body.x >= BUFFER_WIDTH / 2);
+
+    game.set_pterodactyl_delay(1);
+    game.tick();
+    // It came in at the left edge, and has had one tick to swoop from there.
+    let ptero = game.enemies().find(|e| e.etype == EnemyType::Pterodactyl).unwrap();
+    assert!(wrapped_distance(ptero.body.x, 0) <= 4);
+}
+
+#[test]
+fn title_screen_offers_three_modes() {
+    let mut game: Joust<FrameBuffer> = Joust::default();
+    game.tick();
+    assert_eq!(game.mode(), Mode::OnePlayer);
+    assert!(row_text(&game, 17).contains("1 Player     2P Co-op     2P Versus"));
+    press(&mut game, KeyCode::ArrowRight);
+    assert_eq!(game.mode(), Mode::Coop);
+    press(&mut game, KeyCode::ArrowRight);
+    assert_eq!(game.mode(), Mode::Versus);
+    press(&mut game, KeyCode::ArrowRight);
+    assert_eq!(game.mode(), Mode::OnePlayer);
+    press(&mut game, KeyCode::ArrowLeft);
+    assert_eq!(game.mode(), Mode::Versus);
+}
+
+#[test]
+fn two_player_games_have_two_riders() {
+    let mut game = new_two_player_game(Mode::Coop);
+    assert_eq!(game.players().count(), 2);
+    game.tick();
+    let lives: Vec<_> = game.players().map(|p| p.lives).collect();
+    assert_eq!(lives, [6, 6]);
+    assert!(row_text(&game, 0).contains("P2"));
+
+    let start = game.players().nth(1).unwrap().body.y;
+    press(&mut game, KeyCode::W);
+    game.tick();
+    assert!(game.players().nth(1).unwrap().body.y < start);
+    assert_eq!(game.player().body.y, 20);
+}
+
+#[test]
+fn versus_players_can_unhorse_each_other() {
+    let mut game = new_two_player_game(Mode::Versus);
+    game.tick();
+    drop_player_two_on_player_one(&mut game);
+    let scores: Vec<_> = game.players().map(|p| p.score).collect();
+    let lives: Vec<_> = game.players().map(|p| p.lives).collect();
+    assert_eq!(lives, [5, 6]);
+    assert_eq!(scores, [50, PLAYER_BOUNTY]);
+}
+
+#[test]
+fn coop_players_bounce_off_each_other() {
+    let mut game = new_two_player_game(Mode::Coop);
+    game.tick();
+    drop_player_two_on_player_one(&mut game);
+    let lives: Vec<_> = game.players().map(|p| p.lives).collect();
+    assert_eq!(lives, [6, 6]);
+    assert!(game.players().nth(1).unwrap().body.dx > 0);
+    assert!(game.players().all(|p| p.recoil > 0));
+}
@@ -0,0 +1,50 @@
+use pluggable_interrupt_joust::{
+    lance::{knock_apart, resolve, Lance, Outcome, KNOCKBACK},
+    rider::Rider,
+    screen::BUFFER_WIDTH,
+};
+
+fn lance(row: isize, dy: isize) -> Lance {
+    Lance { row, dy }
+}
+
+#[test]
+fn the_higher_lance_wins() {
+    assert_eq!(resolve(lance(10, 0), lance(11, 0)), Outcome::First);
+    assert_eq!(resolve(lance(12, 0), lance(11, 0)), Outcome::Second);
+    // Height beats flapping.
+    assert_eq!(resolve(lance(10, 40), lance(11, -40)), Outcome::First);
+}
+
+#[test]
+fn level_lances_go_to_the_rider_flapping_upward() {
+    assert_eq!(resolve(lance(10, -40), lance(10, 40)), Outcome::First);
+    assert_eq!(resolve(lance(10, 40), lance(10, -40)), Outcome::Second);
+    assert_eq!(resolve(lance(10, 0), lance(10, 0)), Outcome::Tie);
+    assert_eq!(resolve(lance(10, -40), lance(10, -20)), Outcome::Tie);
+    assert_eq!(resolve(lance(10, 0), lance(10, 40)), Outcome::Tie);
+}
+
+#[test]
+fn tied_riders_are_knocked_apart() {
+    let (mut left, mut right) = (Rider::body(10, 5), Rider::body(12, 5));
+    (left.dx, right.dx) = (KNOCKBACK / 2, -KNOCKBACK / 2);
+    knock_apart(&mut left, &mut right);
+    assert_eq!((left.dx, right.dx), (-KNOCKBACK, KNOCKBACK));
+    knock_apart(&mut right, &mut left);
+    assert_eq!((left.dx, right.dx), (-KNOCKBACK, KNOCKBACK));
+
+    // Across the wrap, the rider at the right edge is the one on the left.
+    let (mut edge, mut start) = (Rider::body(BUFFER_WIDTH - 2, 5), Rider::body(1, 5));
+    knock_apart(&mut edge, &mut start);
+    assert_eq!((edge.dx, start.dx), (-KNOCKBACK, KNOCKBACK));
+
+    // In the same column, each keeps the way it was going.
+    let (mut fast, mut slow) = (Rider::body(20, 5), Rider::body(20, 5));
+    (fast.dx, slow.dx) = (KNOCKBACK / 2, -KNOCKBACK / 2);
+    knock_apart(&mut fast, &mut slow);
+    assert_eq!((fast.dx, slow.dx), (KNOCKBACK, -KNOCKBACK));
+    (fast.dx, slow.dx) = (-KNOCKBACK / 2, KNOCKBACK / 2);
+    knock_apart(&mut fast, &mut slow);
+    assert_eq!((fast.dx, slow.dx), (-KNOCKBACK, KNOCKBACK));
+}
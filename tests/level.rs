@@ -0,0 +1,88 @@
+use pluggable_interrupt_joust::level::{Level, ParseError, ParseErrorKind, LEVELS};
+
+const SMALL: &str = "\
+..S
+P  P
+####..~~~
+";
+
+fn error(map: &str) -> ParseError {
+    Level::parse(map).unwrap_err()
+}
+
+#[test]
+fn arena_map_matches_the_original_layout() {
+    let arena = Level::parse(include_str!("../src/levels/arena.txt")).unwrap();
+    let mut platforms = arena.platforms().to_vec();
+    platforms.sort();
+    assert_eq!(
+        platforms,
+        [(0, 12, 15, 13), (20, 23, 60, 24), (35, 7, 50, 8), (60, 11, 70, 12), (70, 12, 80, 13)]
+    );
+    assert_eq!(arena.lava(), [(0, 24, 21), (59, 24, 80)]);
+    assert_eq!(arena.player_starts(), [(39, 20), (50, 20)]);
+    assert_eq!(arena.spawn_points(), [(7, 9), (74, 9), (39, 20), (42, 4)]);
+    assert_eq!(arena, LEVELS[0]);
+}
+
+#[test]
+fn small_map_parses() {
+    let level = Level::parse(SMALL).unwrap();
+    assert_eq!(level.platforms(), [(0, 2, 4, 3)]);
+    assert_eq!(level.lava(), [(6, 2, 9)]);
+    assert_eq!(level.spawn_points(), [(2, 0)]);
+    assert_eq!(level.player_starts(), [(0, 1), (3, 1)]);
+}
+
+#[test]
+fn numbered_spawn_points_come_first_in_order() {
+    let level = Level::parse("S2.1\nc..P\n####\n").unwrap();
+    assert_eq!(level.spawn_points(), [(3, 0), (1, 0), (0, 1), (0, 0)]);
+    assert_eq!(level.player_starts(), [(0, 1), (3, 1)]);
+    let e = error("1..1\nPP\n");
+    assert_eq!((e.line, e.column, e.kind), (1, 4, ParseErrorKind::DuplicateSpawnPoint(b'1')));
+}
+
+#[test]
+fn crlf_line_endings_are_accepted() {
+    assert_eq!(Level::parse(&SMALL.replace('\n', "\r\n")), Level::parse(SMALL));
+}
+
+#[test]
+fn stacked_runs_merge_into_one_platform() {
+    let level = Level::parse("S\nPP\n ###\n ###\n ##\n").unwrap();
+    assert_eq!(level.platforms(), [(1, 2, 4, 4), (1, 4, 3, 5)]);
+}
+
+#[test]
+fn unknown_tiles_are_reported_where_they_are() {
+    let e = error("S\nP P\n ##x#\n");
+    assert_eq!((e.line, e.column, e.kind), (3, 4, ParseErrorKind::UnknownTile(b'x')));
+    assert_eq!(e.to_string(), "line 3, column 4: unknown tile 'x'");
+}
+
+#[test]
+fn oversized_maps_are_rejected() {
+    let wide = format!("SPP\n{}\n", "#".repeat(81));
+    let e = error(&wide);
+    assert_eq!((e.line, e.column, e.kind), (2, 81, ParseErrorKind::LineTooLong));
+
+    let tall = format!("SPP{}#\n", "\n".repeat(25));
+    let e = error(&tall);
+    assert_eq!((e.line, e.column, e.kind), (26, 1, ParseErrorKind::TooManyLines));
+}
+
+#[test]
+fn too_many_parts_are_rejected() {
+    let e = error("SPP\n# # # # # # # # # # # # #\n");
+    assert_eq!((e.line, e.column, e.kind), (2, 25, ParseErrorKind::TooManyPlatforms));
+
+    let e = error("SPPP\n");
+    assert_eq!((e.line, e.column, e.kind), (1, 4, ParseErrorKind::TooManyPlayerStarts));
+}
+
+#[test]
+fn maps_need_spawn_points_and_player_starts() {
+    assert_eq!(error("SP\n##\n").kind, ParseErrorKind::MissingPlayerStart);
+    assert_eq!(error("PP\n##\n").kind, ParseErrorKind::NoSpawnPoints);
+}
@@ -0,0 +1,108 @@
+use pc_keyboard::KeyCode;
+use pluggable_interrupt_joust::{screen::FrameBuffer, Joust, State};
+
+mod common;
+
+use common::press;
+
+fn new_game() -> Joust<FrameBuffer> {
+    let mut game = common::new_game();
+    for _ in 0..20 {
+        game.tick();
+    }
+    game
+}
+
+fn row_text(game: &Joust<FrameBuffer>, row: usize) -> String {
+    game.screen().row(row).collect()
+}
+
+/// Picks the pause menu item `down` places below the top one.
+fn choose(game: &mut Joust<FrameBuffer>, down: usize) {
+    for _ in 0..down {
+        press(game, KeyCode::ArrowDown);
+    }
+    press(game, KeyCode::Return);
+    game.tick();
+}
+
+#[test]
+fn pausing_freezes_play() {
+    let mut game = new_game();
+    press(&mut game, KeyCode::Escape);
+    assert_eq!(game.state(), State::Paused);
+    let at = |game: &Joust<FrameBuffer>| (game.player().body.x, game.player().body.y);
+    let (player, enemies) = (at(&game), game.enemies().count());
+    for _ in 0..100 {
+        game.tick();
+    }
+    press(&mut game, KeyCode::X);
+    assert_eq!(at(&game), player);
+    assert_eq!(game.enemies().count(), enemies);
+    assert!(row_text(&game, 8).contains("PAUSED"));
+
+    press(&mut game, KeyCode::P);
+    assert_eq!(game.state(), State::Playing);
+    press(&mut game, KeyCode::X);
+    game.tick();
+    assert_ne!(at(&game), player);
+}
+
+#[test]
+fn resuming_restores_the_playfield() {
+    let mut game = new_game();
+    let before = *game.screen();
+    press(&mut game, KeyCode::P);
+    game.tick();
+    press(&mut game, KeyCode::ArrowDown);
+    game.tick();
+    assert!(*game.screen() != before);
+
+    press(&mut game, KeyCode::ArrowUp);
+    choose(&mut game, 0);
+    assert_eq!(game.state(), State::Playing);
+    assert!(!row_text(&game, 8).contains("PAUSED"));
+    // Apart from the sprites that have since moved, the screen is as it was.
+    let mut resumed: Joust<FrameBuffer> = new_game();
+    resumed.tick();
+    assert!(*game.screen() == *resumed.screen());
+}
+
+#[test]
+fn restart_starts_a_new_game() {
+    let mut game = new_game();
+    press(&mut game, KeyCode::X);
+    game.tick();
+    press(&mut game, KeyCode::Escape);
+    choose(&mut game, 1);
+    assert_eq!(game.state(), State::Playing);
+    assert_eq!(game.wave(), 1);
+    assert_eq!(game.enemies().count(), 1);
+}
+
+#[test]
+fn quit_goes_back_to_the_title_screen() {
+    let mut game = new_game();
+    press(&mut game, KeyCode::Escape);
+    choose(&mut game, 3);
+    assert_eq!(game.state(), State::TitleScreen);
+    assert!(row_text(&game, 20).contains("Press Z to start"));
+}
+
+#[test]
+fn the_sound_page_toggles_the_sound() {
+    let mut game = new_game();
+    press(&mut game, KeyCode::Escape);
+    choose(&mut game, 2);
+    assert!(row_text(&game, 8).contains("SOUND"));
+    assert!(row_text(&game, 10).contains("Sound: on"));
+
+    choose(&mut game, 0);
+    assert!(game.is_muted());
+    assert!(row_text(&game, 10).contains("Sound: off"));
+
+    press(&mut game, KeyCode::Escape);
+    game.tick();
+    assert!(row_text(&game, 8).contains("PAUSED"));
+    assert_eq!(game.state(), State::Paused);
+}
@@ -0,0 +1,139 @@
+use pc_keyboard::{DecodedKey, KeyCode, KeyState};
+use pluggable_interrupt_joust::{
+    body::tenths,
+    config::GameConfig,
+    input::KeyInput,
+    replay::{CHECKSUM_INTERVAL, MAX_EVENTS},
+    screen::FrameBuffer,
+    Joust, State,
+};
+
+mod common;
+
+use common::{new_game, press};
+
+const KEYS: [KeyCode; 8] = [
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::X,
+    KeyCode::A,
+    KeyCode::D,
+    KeyCode::W,
+    KeyCode::Z,
+    KeyCode::Return,
+];
+
+/// Plays a two-player game for `steps` steps, pressing and releasing keys
+/// picked by a simple linear congruential generator.
+fn play(steps: usize) -> Joust<FrameBuffer> {
+    let mut game: Joust<FrameBuffer> = Joust::default();
+    game.tick();
+    press(&mut game, KeyCode::ArrowRight);
+    press(&mut game, KeyCode::Z);
+    let mut seed: u32 = 7;
+    for _ in 0..steps {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        if seed >> 29 < 3 {
+            let code = KEYS[(seed >> 16) as usize % KEYS.len()];
+            game.key_event(code, KeyState::Down);
+            if code == KeyCode::Return {
+                game.key(DecodedKey::Unicode('\n'));
+            }
+            // Hold one key in four down into the next step.
+            if seed & 3 << 14 != 0 {
+                game.key_event(code, KeyState::Up);
+            }
+        }
+        game.tick();
+    }
+    game
+}
+
+fn replay_of(game: &Joust<FrameBuffer>) -> Joust<FrameBuffer> {
+    let mut replay: Joust<FrameBuffer> = Joust::default();
+    replay.replay(game.recording());
+    replay
+}
+
+#[test]
+fn a_game_is_recorded() {
+    let game = play(1000);
+    let recording = game.recording();
+    assert_eq!(recording.steps(), 1001);
+    assert_eq!(recording.checksums().len(), 1001 / CHECKSUM_INTERVAL as usize);
+    assert_eq!(recording.events()[0], (1, KeyInput::Event(KeyCode::ArrowRight, KeyState::Down)));
+    assert!(recording.events().len() > 500);
+    assert!(!recording.is_full());
+}
+
+#[test]
+fn replays_reproduce_the_game_exactly() {
+    let game = play(1000);
+    assert_eq!(game.state(), State::Playing);
+    let mut replay = replay_of(&game);
+    while replay.is_replaying() {
+        replay.tick();
+    }
+    assert_eq!(replay.desync(), None);
+    assert_eq!(replay.checksum(), game.checksum());
+    assert!(replay == game);
+}
+
+#[test]
+fn live_keys_are_ignored_during_a_replay() {
+    let game = play(200);
+    let mut replay = replay_of(&game);
+    while replay.is_replaying() {
+        press(&mut replay, KeyCode::X);
+        replay.tick();
+    }
+    assert!(replay == game);
+}
+
+#[test]
+fn a_drifting_replay_is_caught() {
+    let game = play(1000);
+    let mut replay = replay_of(&game);
+    replay.set_pterodactyl_delay(1);
+    while replay.is_replaying() {
+        replay.tick();
+    }
+    let desync = replay.desync().expect("replay should drift");
+    assert_eq!(desync % CHECKSUM_INTERVAL, 0);
+}
+
+#[test]
+fn the_checksum_covers_the_physics() {
+    let game: Joust<FrameBuffer> = Joust::default();
+    let mut floaty: Joust<FrameBuffer> = Joust::default();
+    let mut config = GameConfig::default();
+    config.physics.gravity = tenths(2);
+    floaty.set_config(config);
+    assert_ne!(floaty.checksum(), game.checksum());
+}
+
+#[test]
+fn held_keys_are_recorded_once() {
+    let mut game = new_game();
+    let before = game.recording().events().len();
+    for _ in 0..2000 {
+        game.key_event(KeyCode::ArrowLeft, KeyState::Down);
+        game.key(DecodedKey::RawKey(KeyCode::ArrowLeft));
+        game.tick();
+    }
+    game.key_event(KeyCode::ArrowLeft, KeyState::Up);
+    game.key_event(KeyCode::ArrowLeft, KeyState::Up);
+    assert_eq!(game.recording().events().len(), before + 2);
+}
+
+#[test]
+fn recording_stops_when_full() {
+    let mut game: Joust<FrameBuffer> = Joust::default();
+    for _ in 0..=MAX_EVENTS {
+        press(&mut game, KeyCode::Q);
+        game.tick();
+    }
+    assert!(game.recording().is_full());
+    assert_eq!(game.recording().events().len(), MAX_EVENTS);
+    assert!(game.recording().steps() < MAX_EVENTS as u32 + 1);
+}
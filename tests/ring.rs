@@ -0,0 +1,67 @@
+use std::thread;
+
+use pluggable_interrupt_joust::ring::Ring;
+
+#[test]
+fn values_come_out_in_order() {
+    let ring: Ring<u32, 4> = Ring::new();
+    unsafe {
+        assert_eq!(ring.pop(), None);
+        // Go round the buffer a few times.
+        for i in 0..10 {
+            ring.push(i).unwrap();
+            ring.push(i + 100).unwrap();
+            assert_eq!(ring.len(), 2);
+            assert_eq!(ring.pop(), Some(i));
+            assert_eq!(ring.pop(), Some(i + 100));
+        }
+        assert!(ring.is_empty());
+    }
+}
+
+#[test]
+fn a_full_ring_counts_overflows() {
+    let ring: Ring<u8, 3> = Ring::new();
+    unsafe {
+        for i in 0..3 {
+            ring.push(i).unwrap();
+        }
+        assert_eq!(ring.push(3), Err(3));
+        assert_eq!(ring.push(4), Err(4));
+        assert_eq!(ring.overflows(), 2);
+        assert_eq!(ring.len(), ring.capacity());
+
+        assert_eq!(ring.pop(), Some(0));
+        ring.push(5).unwrap();
+        assert_eq!([ring.pop(), ring.pop(), ring.pop()], [Some(1), Some(2), Some(5)]);
+    }
+    assert_eq!(ring.overflows(), 2);
+}
+
+#[test]
+fn one_producer_and_one_consumer_on_separate_threads() {
+    const COUNT: u64 = 1_000_000;
+    static RING: Ring<u64, 64> = Ring::new();
+
+    let producer = thread::spawn(|| {
+        for i in 0..COUNT {
+            // SAFETY: this thread is the only producer.
+            while unsafe { RING.push(i) }.is_err() {
+                thread::yield_now();
+            }
+        }
+    });
+    let mut expected = 0;
+    while expected < COUNT {
+        // SAFETY: this thread is the only consumer.
+        match unsafe { RING.pop() } {
+            Some(value) => {
+                assert_eq!(value, expected);
+                expected += 1;
+            }
+            None => thread::yield_now(),
+        }
+    }
+    producer.join().unwrap();
+    assert!(RING.is_empty());
+}
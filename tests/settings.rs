@@ -0,0 +1,137 @@
+use pc_keyboard::KeyCode;
+use pluggable_interrupt_joust::{
+    config::{Action, Difficulty, GameConfig, MAX_LIVES},
+    enemy::EnemyType,
+    screen::FrameBuffer,
+    Joust, State,
+};
+
+mod common;
+
+use common::press;
+
+/// Presses `code` `times` times, then runs a tick.
+fn tap(game: &mut Joust<FrameBuffer>, code: KeyCode, times: usize) {
+    for _ in 0..times {
+        press(game, code);
+    }
+    game.tick();
+}
+
+fn row_text(game: &Joust<FrameBuffer>, row: usize) -> String {
+    game.screen().row(row).collect()
+}
+
+fn settings() -> Joust<FrameBuffer> {
+    let mut game = Joust::default();
+    game.tick();
+    tap(&mut game, KeyCode::S, 1);
+    game
+}
+
+#[test]
+fn settings_open_from_the_title_screen() {
+    let mut game = settings();
+    assert_eq!(game.state(), State::Settings);
+    assert!(row_text(&game, 2).contains("SETTINGS"));
+    assert!(row_text(&game, 5).contains("Normal"));
+    assert!(row_text(&game, 9).contains("P1 flap") && row_text(&game, 9).contains(" X "));
+
+    press(&mut game, KeyCode::Escape);
+    assert_eq!(game.state(), State::TitleScreen);
+}
+
+#[test]
+fn difficulty_and_lives_are_adjustable() {
+    let mut game = settings();
+    tap(&mut game, KeyCode::ArrowRight, 1);
+    assert_eq!(game.config().difficulty, Difficulty::Hard);
+    assert!(row_text(&game, 5).contains("Hard"));
+
+    tap(&mut game, KeyCode::ArrowDown, 1);
+    tap(&mut game, KeyCode::ArrowLeft, 3);
+    assert_eq!(game.config().lives, 3);
+    tap(&mut game, KeyCode::ArrowRight, 20);
+    assert_eq!(game.config().lives, MAX_LIVES);
+
+    tap(&mut game, KeyCode::ArrowLeft, 6);
+    press(&mut game, KeyCode::Escape);
+    press(&mut game, KeyCode::Z);
+    assert_eq!(game.lives(), 3);
+}
+
+#[test]
+fn actions_can_be_rebound() {
+    let mut game = settings();
+    tap(&mut game, KeyCode::ArrowDown, 4);
+    tap(&mut game, KeyCode::Return, 1);
+    assert!(row_text(&game, 9).contains("press a key"));
+    tap(&mut game, KeyCode::Spacebar, 1);
+    assert!(row_text(&game, 9).contains("Spacebar"));
+    assert_eq!(game.config().key(Action::Flap1), KeyCode::Spacebar);
+
+    press(&mut game, KeyCode::Escape);
+    press(&mut game, KeyCode::Z);
+    let y = game.player().body.y;
+    tap(&mut game, KeyCode::X, 1);
+    assert_eq!(game.player().body.y, y);
+    tap(&mut game, KeyCode::Spacebar, 1);
+    assert!(game.player().body.y < y);
+}
+
+#[test]
+fn escape_cancels_a_rebind() {
+    let mut game = settings();
+    tap(&mut game, KeyCode::ArrowDown, 2);
+    press(&mut game, KeyCode::Return);
+    press(&mut game, KeyCode::Escape);
+    assert_eq!(game.state(), State::Settings);
+    assert_eq!(game.config().key(Action::Left1), KeyCode::ArrowLeft);
+}
+
+#[test]
+fn menu_keys_beat_bindings() {
+    let mut game = settings();
+    tap(&mut game, KeyCode::ArrowDown, 10);
+    press(&mut game, KeyCode::Return);
+    press(&mut game, KeyCode::ArrowUp);
+    assert_eq!(game.config().key(Action::Mute), KeyCode::ArrowUp);
+    press(&mut game, KeyCode::ArrowUp);
+    tap(&mut game, KeyCode::Return, 1);
+    assert!(row_text(&game, 14).contains("press a key"));
+    assert!(!game.is_muted());
+
+    tap(&mut game, KeyCode::Escape, 1);
+    press(&mut game, KeyCode::Escape);
+    press(&mut game, KeyCode::Z);
+    tap(&mut game, KeyCode::Escape, 1);
+    tap(&mut game, KeyCode::ArrowUp, 1);
+    assert_eq!(game.state(), State::Paused);
+    assert!(!game.is_muted());
+}
+
+#[test]
+fn binding_a_taken_key_swaps_the_two() {
+    let mut config = GameConfig::default();
+    config.bind(Action::Flap1, KeyCode::W);
+    assert_eq!(config.key(Action::Flap1), KeyCode::W);
+    assert_eq!(config.key(Action::Flap2), KeyCode::X);
+    assert_eq!(config.action(KeyCode::X), Some(Action::Flap2));
+}
+
+#[test]
+fn easy_enemies_fly_slower() {
+    let mut game: Joust<FrameBuffer> = Joust::default();
+    let mut config = GameConfig::default();
+    config.difficulty = Difficulty::Easy;
+    game.set_config(config);
+    game.tick();
+    press(&mut game, KeyCode::Z);
+    let cap = Difficulty::Easy.speed_cap(EnemyType::Bounder);
+    for _ in 0..200 {
+        game.tick();
+        for enemy in game.enemies().filter(|enemy| enemy.etype == EnemyType::Bounder) {
+            assert!(enemy.body.dx.abs() <= cap);
+        }
+    }
+}
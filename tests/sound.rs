@@ -0,0 +1,83 @@
+use pc_keyboard::KeyCode;
+use pluggable_interrupt_joust::{
+    sound::{Effect, Sequencer},
+    State,
+};
+
+mod common;
+
+use common::{new_game, press};
+
+/// The tones `sequencer` sounds over its next `steps` steps.
+fn tones(sequencer: &mut Sequencer, steps: usize) -> Vec<Option<u16>> {
+    (0..steps)
+        .map(|_| {
+            sequencer.tick();
+            sequencer.tone()
+        })
+        .collect()
+}
+
+#[test]
+fn effects_play_note_by_note() {
+    let mut sequencer = Sequencer::default();
+    sequencer.play(Effect::JoustWin);
+    assert_eq!(
+        tones(&mut sequencer, 5),
+        [Some(523), Some(659), Some(784), Some(784), None]
+    );
+}
+
+#[test]
+fn important_effects_cut_in() {
+    let mut sequencer = Sequencer::default();
+    sequencer.play(Effect::Death);
+    sequencer.play(Effect::Flap);
+    assert_eq!(tones(&mut sequencer, 1), [Some(392)]);
+    sequencer.play(Effect::GameOver);
+    assert_eq!(tones(&mut sequencer, 2), [Some(392), Some(392)]);
+    assert_eq!(tones(&mut sequencer, 2), [Some(392), None]);
+}
+
+#[test]
+fn flapping_chirps() {
+    let mut game = new_game();
+    press(&mut game, KeyCode::X);
+    game.tick();
+    assert_eq!(game.tone(), Some(880));
+    game.tick();
+    assert_eq!(game.tone(), None);
+}
+
+#[test]
+fn m_mutes_the_game() {
+    let mut game = new_game();
+    press(&mut game, KeyCode::M);
+    assert!(game.is_muted());
+    press(&mut game, KeyCode::X);
+    game.tick();
+    assert_eq!(game.tone(), None);
+    press(&mut game, KeyCode::M);
+    assert!(!game.is_muted());
+}
+
+#[test]
+fn deaths_and_game_over_have_their_own_tunes() {
+    let mut game = new_game();
+    let mut heard = Vec::new();
+    while game.state() == State::Playing {
+        game.tick();
+        heard.push(game.tone());
+    }
+    assert!(heard.contains(&Some(196)));
+
+    // The tune starts on the step the game ends.
+    let mut ending = vec![*heard.last().unwrap()];
+    for _ in 1..21 {
+        game.tick();
+        ending.push(game.tone());
+    }
+    let mut sequencer = Sequencer::default();
+    sequencer.play(Effect::GameOver);
+    assert_eq!(ending, tones(&mut sequencer, 21));
+}
@@ -0,0 +1,57 @@
+use pluggable_interrupt_joust::{
+    body::SUBCELL,
+    screen::BUFFER_WIDTH,
+    sweep::{meet, sweep, Aabb, Contact, Move},
+};
+
+/// A `width` by `height` box with its top-left corner at cell `x`, `y`.
+fn cells(x: usize, y: usize, width: usize, height: usize) -> Aabb {
+    Aabb::cells((x, y, x + width, y + height))
+}
+
+#[test]
+fn sweeps_find_the_first_side_hit() {
+    let platform = cells(10, 10, 20, 1);
+    // Falling onto the top, a quarter of the way down a four-row drop.
+    assert_eq!(
+        sweep(cells(12, 6, 4, 3), (0, 4 * SUBCELL), platform),
+        Some(Contact { time: SUBCELL / 4, normal: (0, -1) })
+    );
+    // Rising into the underside.
+    assert_eq!(
+        sweep(cells(12, 12, 4, 3), (0, -2 * SUBCELL), platform),
+        Some(Contact { time: SUBCELL / 2, normal: (0, 1) })
+    );
+    // Running into the left end.
+    assert_eq!(
+        sweep(cells(4, 9, 4, 3), (4 * SUBCELL, 0), platform),
+        Some(Contact { time: SUBCELL / 2, normal: (-1, 0) })
+    );
+    // Sliding along the top, or stopping short, isn't a hit.
+    assert_eq!(sweep(cells(12, 7, 4, 3), (4 * SUBCELL, 0), platform), None);
+    assert_eq!(sweep(cells(12, 2, 4, 3), (0, 4 * SUBCELL), platform), None);
+}
+
+#[test]
+fn sweeps_reach_across_the_wrap() {
+    let platform = cells(0, 10, 5, 1);
+    let contact = sweep(cells(BUFFER_WIDTH - 6, 9, 4, 3), (4 * SUBCELL, 0), platform);
+    assert_eq!(contact, Some(Contact { time: SUBCELL / 2, normal: (-1, 0) }));
+}
+
+#[test]
+fn riders_passing_through_each_other_still_meet() {
+    // Each covers eight cells in one go: enough to swap places without ever
+    // overlapping at either end.
+    let a = Move { from: cells(10, 5, 4, 3), by: (8 * SUBCELL, 0) };
+    let b = Move { from: cells(18, 5, 4, 3), by: (-8 * SUBCELL, 0) };
+    assert!(!a.at(SUBCELL).overlaps(&b.at(SUBCELL)));
+    let contact = meet(a, b).unwrap();
+    assert_eq!(contact, Contact { time: SUBCELL / 4, normal: (-1, 0) });
+    assert_eq!((a.cell_at(contact.time), b.cell_at(contact.time)), ((12, 5), (16, 5)));
+
+    // Riders already overlapping meet straight away.
+    let still = Move { from: cells(11, 6, 4, 3), by: (0, 0) };
+    assert_eq!(meet(a, still), Some(Contact { time: 0, normal: (0, 0) }));
+    assert_eq!(Move::between(cells(BUFFER_WIDTH - 1, 0, 1, 1), cells(1, 0, 1, 1)).by, (2 * SUBCELL, 0));
+}
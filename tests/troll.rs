@@ -0,0 +1,121 @@
+use pc_keyboard::KeyCode;
+use pluggable_interrupt_joust::{
+    body::Physics,
+    event::Event,
+    rider::Rider,
+    screen::FrameBuffer,
+    troll::{Hand, Victim, DRAG, GRIP, REACH, REST_TICKS},
+    Joust,
+};
+
+mod common;
+
+use common::{new_game, press};
+
+/// Starts a game on `wave` and runs the player left off the platform until
+/// the troll grabs them.
+fn grabbed_on_wave(wave: usize) -> Joust<FrameBuffer> {
+    let mut game = new_game();
+    game.command(&format!("wave {wave}"), &mut String::new()).unwrap();
+    for _ in 0..100 {
+        press(&mut game, KeyCode::ArrowLeft);
+        game.tick();
+        if core::iter::from_fn(|| game.next_event()).any(|event| event == Event::TrollGrabbed(Victim::Player(0))) {
+            return game;
+        }
+    }
+    panic!("the troll never grabbed the player");
+}
+
+#[test]
+fn the_hand_reaches_riders_flying_low_over_a_pit() {
+    let lava = [(0, 24, 21)];
+    let mut body = Rider::body(10, 24 - REACH - 3);
+    assert_eq!(Hand::reaches(&body, &lava), None);
+    body.y += 1;
+    assert_eq!(Hand::reaches(&body, &lava), Some(24));
+    // Only over the pit, and not while standing on a platform.
+    body.x = 21;
+    assert_eq!(Hand::reaches(&body, &lava), None);
+    body.x = 78;
+    assert_eq!(Hand::reaches(&body, &lava), Some(24));
+    body.on_ground = true;
+    assert_eq!(Hand::reaches(&body, &lava), None);
+}
+
+#[test]
+fn flapping_hard_enough_breaks_the_grip() {
+    let mut hand = Hand::default();
+    let mut body = Rider::body(10, 20);
+    body.dx = 100;
+    hand.grab(Victim::Player(0), &mut body, 24);
+    assert_eq!((body.dx, body.dy), (0, DRAG));
+    assert_eq!((hand.x, hand.y), (12, 23));
+
+    body.dy = -GRIP / 2;
+    assert!(!hand.hold(&mut body));
+    assert_eq!(body.dy, DRAG);
+    body.dy = -GRIP / 2;
+    assert!(hand.hold(&mut body));
+    assert_eq!(hand.held, None);
+    assert_eq!(hand.rest, REST_TICKS);
+}
+
+/// How many flaps of `flap` it takes to break free of the hand.
+fn flaps_to_break_free(flap: isize) -> usize {
+    let mut hand = Hand::default();
+    let mut body = Rider::body(10, 20);
+    hand.grab(Victim::Enemy(0), &mut body, 24);
+    (1..)
+        .find(|_| {
+            body.dy = -flap;
+            hand.hold(&mut body)
+        })
+        .unwrap()
+}
+
+#[test]
+fn players_need_four_flaps_and_enemies_three() {
+    let physics = Physics::default();
+    assert_eq!(flaps_to_break_free(physics.player_flap), 4);
+    assert_eq!(flaps_to_break_free(physics.enemy_flap), 3);
+}
+
+#[test]
+fn the_troll_drags_riders_into_the_lava() {
+    let mut game = grabbed_on_wave(4);
+    assert_eq!(game.player().body.dx, 0);
+    let feet = game.player().body.y + 3;
+    let arm: String = game.screen().row(feet).collect();
+    assert!(arm.contains('W'));
+    for _ in 0..50 {
+        if game.lives() < 6 {
+            break;
+        }
+        game.tick();
+    }
+    assert_eq!(game.lives(), 5);
+}
+
+#[test]
+fn a_grabbed_player_can_flap_free() {
+    let mut game = grabbed_on_wave(4);
+    for _ in 0..10 {
+        press(&mut game, KeyCode::X);
+        game.tick();
+    }
+    let events: Vec<_> = core::iter::from_fn(|| game.next_event()).collect();
+    assert!(events.contains(&Event::BrokeFree(Victim::Player(0))));
+    assert_eq!(game.lives(), 6);
+}
+
+#[test]
+fn early_waves_have_no_troll() {
+    let mut game = new_game();
+    for _ in 0..100 {
+        press(&mut game, KeyCode::ArrowLeft);
+        game.tick();
+    }
+    let events: Vec<_> = core::iter::from_fn(|| game.next_event()).collect();
+    assert!(!events.iter().any(|event| matches!(event, Event::TrollGrabbed(_))));
+}
@@ -0,0 +1,97 @@
+use pluggable_interrupt_joust::{
+    enemy::EnemyType,
+    screen::FrameBuffer,
+    wave::{Rule, Wave, BANNER_TICKS, LOOP, MAX_EGGS, MAX_RIDERS, WAVES},
+    Joust,
+};
+
+mod common;
+
+use common::new_game;
+
+/// Starts wave `wave` and runs until its banner comes down.
+fn skip_to_wave(game: &mut Joust<FrameBuffer>, wave: usize) {
+    game.command(&format!("wave {wave}"), &mut String::new()).unwrap();
+    for _ in 0..BANNER_TICKS + 1 {
+        game.tick();
+    }
+}
+
+fn row_text(game: &Joust<FrameBuffer>, row: usize) -> String {
+    game.screen().row(row).collect()
+}
+
+#[test]
+fn waves_past_the_table_keep_getting_harder() {
+    assert_eq!(Wave::number(1), WAVES[0]);
+    assert_eq!(Wave::number(WAVES.len()), WAVES[WAVES.len() - 1]);
+    for number in WAVES.len() + 1..200 {
+        let (wave, last_lap) = (Wave::number(number), Wave::number(number - LOOP));
+        assert_eq!(wave.rule, last_lap.rule);
+        assert!(wave.riders() >= last_lap.riders());
+        assert!(wave.spawn_delay <= last_lap.spawn_delay);
+        assert!(wave.riders() <= if wave.rule == Rule::Eggs { MAX_EGGS } else { MAX_RIDERS });
+    }
+    assert!(Wave::number(WAVES.len() + 1).hunters > WAVES[WAVES.len() - LOOP].hunters);
+}
+
+#[test]
+fn far_off_waves_are_all_shadow_lords() {
+    let far = Wave::number(usize::MAX);
+    assert_eq!((far.bounders, far.hunters), (0, 0));
+    assert_eq!(far.riders(), if far.rule == Rule::Eggs { MAX_EGGS } else { MAX_RIDERS });
+    assert_eq!(Wave::number(200_000_001), Wave::number(200_000_001 - 1_000 * LOOP));
+}
+
+#[test]
+fn a_banner_announces_each_wave() {
+    let mut game = new_game();
+    game.command("wave 3", &mut String::new()).unwrap();
+    game.tick();
+    assert!(row_text(&game, 2).contains("WAVE 3"));
+    assert!(row_text(&game, 3).contains("SURVIVAL WAVE"));
+    assert_eq!(game.enemies().count(), 0);
+
+    for _ in 0..BANNER_TICKS {
+        game.tick();
+    }
+    assert!(!row_text(&game, 2).contains("WAVE"));
+    assert_eq!(game.enemies().count(), 1);
+}
+
+#[test]
+fn riders_spawn_in_order_and_spaced_out() {
+    let mut game = new_game();
+    skip_to_wave(&mut game, 4);
+    let wave = Wave::number(4);
+    assert_eq!(game.enemies().count(), 1);
+    for _ in 1..wave.spawn_delay {
+        game.tick();
+    }
+    assert_eq!(game.enemies().count(), 1);
+    for _ in 0..wave.spawn_delay * 3 {
+        game.tick();
+    }
+    let mut types: Vec<_> = game.enemies().map(|e| e.etype).collect();
+    types.sort_by_key(|&etype| etype as usize);
+    assert_eq!(types, [EnemyType::Bounder, EnemyType::Bounder, EnemyType::Bounder, EnemyType::Hunter]);
+}
+
+#[test]
+fn egg_waves_start_as_eggs() {
+    let mut game = new_game();
+    skip_to_wave(&mut game, 5);
+    let wave = Wave::number(5);
+    assert_eq!(wave.rule, Rule::Eggs);
+    assert_eq!(game.enemies().count(), 0);
+    assert_eq!(game.eggs().count(), wave.riders());
+    let types: Vec<_> = game.eggs().map(|egg| egg.etype).collect();
+    assert_eq!(types, (0..wave.riders()).map(|i| wave.etype(i)).collect::<Vec<_>>());
+}
+
+#[test]
+fn pterodactyl_waves_open_with_one() {
+    let mut game = new_game();
+    skip_to_wave(&mut game, 7);
+    assert!(game.enemies().any(|e| e.etype == EnemyType::Pterodactyl));
+}